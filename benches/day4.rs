@@ -0,0 +1,28 @@
+//! Hashing speed of `KeyHasher` against formatting a new string per nonce,
+//! run with `cargo bench --bench day4`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use aoc_2015::day4::{hash_formatted, KeyHasher};
+
+const KEY: &str = "ckczppom";
+const NONCES: u64 = 2_000_000;
+
+fn time<T>(hash: &dyn Fn(u64) -> T) -> Duration {
+    let start = Instant::now();
+    for num in 0..NONCES {
+        black_box(hash(black_box(num)));
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let hasher = KeyHasher::new(KEY);
+    let formatted = time(&|num| hash_formatted(KEY, num));
+    let reused = time(&|num| hasher.hash(num));
+    for (name, elapsed) in [("format! + md5::compute", formatted), ("KeyHasher", reused)] {
+        println!("{:<24} {:>8.1}ms {:>6.2}M hashes/s", name, elapsed.as_secs_f64() * 1000.0,
+                 NONCES as f64 / elapsed.as_secs_f64() / 1e6);
+    }
+    println!("speedup: {:.2}x", formatted.as_secs_f64() / reused.as_secs_f64());
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use toml::Value;
+use crate::{load_string, AocError};
+
+pub const ANSWERS: &str = "data/answers.toml";
+
+/// Expected puzzle answers, keyed by day and part.
+///
+/// The file has a `[dayN]` table per day with `part1`/`part2` entries, which
+/// may be integers or strings.
+pub struct Answers {
+    answers: HashMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn load(filename: &str) -> Result<Answers, AocError> {
+        Answers::parse(&load_string(filename)?)
+    }
+
+    pub fn parse(s: &str) -> Result<Answers, AocError> {
+        let value: Value = s.parse().map_err(|e| AocError::toml(s, e))?;
+        let mut answers = HashMap::new();
+        let days = value.as_table().into_iter().flatten();
+        for (day_key, parts) in days {
+            let day = parse_key(day_key, "day")?;
+            let parts = parts.as_table()
+                .ok_or_else(|| AocError::parse(day_key, "expected a table of parts"))?;
+            for (part_key, answer) in parts {
+                let part = parse_key(part_key, "part")?;
+                let answer = match answer {
+                    Value::Integer(i) => i.to_string(),
+                    Value::String(s) => s.clone(),
+                    _ => return Err(AocError::parse(part_key, "expected integer or string answer"))
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|s| s.as_str())
+    }
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u32, AocError> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| AocError::parse(key, format!("expected '{}N'", prefix)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("[day1]\npart1 = 138\n\n[day11]\npart2 = \"vzcaabcc\"\n").unwrap();
+        assert_eq!(answers.get(1, 1), Some("138"));
+        assert_eq!(answers.get(1, 2), None);
+        assert_eq!(answers.get(11, 2), Some("vzcaabcc"));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Answers::parse("[first]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day1]\npart1 = 1.5\n").is_err());
+        assert!(Answers::parse("[day1\n").is_err());
+        match Answers::parse("[day1]\npart1 = \n") {
+            Err(e) => assert!(e.to_string().starts_with("line 2: "), "{}", e),
+            Ok(_) => panic!("expected a parse error")
+        }
+    }
+}
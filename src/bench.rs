@@ -0,0 +1,74 @@
+use std::fs;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::solver::Runner;
+use crate::{load_string, AocError};
+
+/// Minimum, median and maximum of a set of samples, in nanoseconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: u64,
+    pub median: u64,
+    pub max: u64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Stats {
+        let mut nanos: Vec<u64> = samples.iter()
+            .map(|d| d.as_nanos() as u64)
+            .collect();
+        nanos.sort_unstable();
+        Stats {
+            min: nanos.first().copied().unwrap_or(0),
+            median: nanos.get(nanos.len() / 2).copied().unwrap_or(0),
+            max: nanos.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u32,
+    pub part: u32,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Measurement {
+    /// Median of parse and solve together, used to compare against a baseline.
+    pub fn total(&self) -> u64 {
+        self.parse.median + self.solve.median
+    }
+}
+
+/// Run `part` of a solver `runs` times, timing parse and solve separately.
+pub fn measure(runner: &dyn Runner, day: u32, part: u32, input: &str, runs: usize)
+               -> Result<Measurement, AocError> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut solve = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let timed = runner.run_timed(part, input)?;
+        parse.push(timed.parse);
+        solve.push(timed.solve);
+    }
+    Ok(Measurement { day, part, parse: Stats::new(&parse), solve: Stats::new(&solve) })
+}
+
+pub fn save_baseline(filename: &str, measurements: &[Measurement]) -> Result<(), AocError> {
+    let json = serde_json::to_string_pretty(measurements)
+        .map_err(|e| AocError::parse(filename, e))?;
+    fs::write(filename, json).map_err(|e| AocError::io(filename, None, e))
+}
+
+pub fn load_baseline(filename: &str) -> Result<Vec<Measurement>, AocError> {
+    serde_json::from_str(&load_string(filename)?)
+        .map_err(|e| AocError::parse(filename, &e).at_line(e.line()))
+}
+
+/// Ratio of the current total time against the baseline for the same day and
+/// part, or `None` if the baseline has no such entry.
+pub fn compare(current: &Measurement, baseline: &[Measurement]) -> Option<f64> {
+    baseline.iter()
+        .find(|m| m.day == current.day && m.part == current.part)
+        .map(|m| current.total() as f64 / m.total().max(1) as f64)
+}
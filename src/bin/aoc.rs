@@ -0,0 +1,442 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, Write};
+use std::process;
+use std::time::{Duration, Instant};
+use aoc_2015::load_string;
+use aoc_2015::answers::{Answers, ANSWERS};
+use aoc_2015::bench::{compare, load_baseline, measure, save_baseline, Measurement};
+use aoc_2015::day2::Order;
+use aoc_2015::day3::{Alphabet, Colouring, Delivery, TurnOrder};
+use aoc_2015::day5::{classify, random_strings, RuleSet};
+use aoc_2015::day6::{self, animate, Brightness, FrameFormat, OnOff};
+use aoc_2015::input;
+use aoc_2015::solver::{solver, DAYS};
+
+const USAGE: &str = "usage: aoc --day N [--part P] [--input path|-]
+       aoc verify [--day N] [--answers path]
+       aoc bench [--day N] [--part P] [--runs N] [--save path] [--baseline path] [--threshold pct]
+       aoc generate --day 2|5 [--count N] [--max-dim N] [--seed N]
+       aoc render --day 3 [--input path|-] [--santas N] [--alphabet name|path] [--format ascii|ppm|png]
+                  [--colour agent|visits] [--scale N] [--output path]
+       aoc explain --day 5 [--part P] [--input path|-]
+       aoc classify --day 5 [--input path|-]
+       aoc animate --day 6 --output path [--part P] [--input path|-] [--every N] [--format pbm|pgm|gif]";
+
+enum Command {
+    Run { day: u32, parts: Vec<u32>, input: String },
+    Verify { days: Vec<u32>, answers: String },
+    Bench { days: Vec<u32>, parts: Vec<u32>, options: BenchOptions },
+    Generate { day: u32, options: GenerateOptions },
+    Render { day: u32, input: String, options: RenderOptions },
+    Explain { day: u32, parts: Vec<u32>, input: String },
+    Classify { day: u32, input: String },
+    Animate { day: u32, part: u32, input: String, options: AnimateOptions },
+}
+
+struct BenchOptions {
+    runs: usize,
+    save: Option<String>,
+    baseline: Option<String>,
+    /// Allowed slowdown against the baseline, in percent.
+    threshold: f64,
+}
+
+struct GenerateOptions {
+    count: usize,
+    max_dim: u32,
+    seed: u64,
+}
+
+struct RenderOptions {
+    santas: usize,
+    /// Name of a preset or a TOML file with the moves.
+    alphabet: String,
+    format: String,
+    colouring: Colouring,
+    scale: usize,
+    output: Option<String>,
+}
+
+struct AnimateOptions {
+    every: usize,
+    format: FrameFormat,
+    /// Directory for PBM/PGM frames, file for a GIF.
+    output: String,
+}
+
+fn usage(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}", msg);
+    process::exit(1);
+}
+
+fn input_for(day: u32) -> String {
+    format!("data/day{}.txt", day)
+}
+
+fn parse_value<T: std::str::FromStr>(arg: &str, value: &str) -> T {
+    value.parse().unwrap_or_else(|_| usage(&format!("invalid value for {}: {}", arg, value)))
+}
+
+fn parse_args(args: &[String]) -> Command {
+    let mode = match args.first().map(|a| a.as_str()) {
+        Some("verify") => "verify",
+        Some("bench") => "bench",
+        Some("generate") => "generate",
+        Some("render") => "render",
+        Some("explain") => "explain",
+        Some("classify") => "classify",
+        Some("animate") => "animate",
+        _ => "run"
+    };
+    let mut day: Option<u32> = None;
+    let mut part: Option<u32> = None;
+    let mut input: Option<String> = None;
+    let mut answers: Option<String> = None;
+    let mut options = BenchOptions { runs: 10, save: None, baseline: None, threshold: 10.0 };
+    let mut generate = GenerateOptions { count: 1000, max_dim: 30, seed: 2015 };
+    let mut every: usize = 1;
+    let mut format = FrameFormat::Gif;
+    let mut output: Option<String> = None;
+    let mut render = RenderOptions {
+        santas: 1, alphabet: "arrows".to_string(), format: "ascii".to_string(), colouring: Colouring::ByAgent, scale: 1, output: None,
+    };
+    let mut it = args.iter().skip(if mode == "run" { 0 } else { 1 });
+    while let Some(arg) = it.next() {
+        let value = match it.next() {
+            Some(v) => v,
+            None => usage(&format!("missing value for {}", arg))
+        };
+        match (mode, arg.as_str()) {
+            (_, "--day") => day = Some(parse_value(arg, value)),
+            ("run", "--part") | ("bench", "--part") | ("explain", "--part") | ("animate", "--part") => part = Some(parse_value(arg, value)),
+            ("run", "--input") | ("render", "--input") | ("explain", "--input") | ("classify", "--input") | ("animate", "--input") => input = Some(value.clone()),
+            ("verify", "--answers") => answers = Some(value.clone()),
+            ("bench", "--runs") => options.runs = parse_value(arg, value),
+            ("bench", "--save") => options.save = Some(value.clone()),
+            ("bench", "--baseline") => options.baseline = Some(value.clone()),
+            ("bench", "--threshold") => options.threshold = parse_value(arg, value),
+            ("generate", "--count") => generate.count = parse_value(arg, value),
+            ("generate", "--max-dim") => generate.max_dim = parse_value(arg, value),
+            ("generate", "--seed") => generate.seed = parse_value(arg, value),
+            ("render", "--santas") => render.santas = parse_value(arg, value),
+            ("render", "--alphabet") => render.alphabet = value.clone(),
+            ("render", "--format") => match value.as_str() {
+                "ascii" | "ppm" | "png" => render.format = value.clone(),
+                _ => usage(&format!("invalid value for {}: {}", arg, value))
+            },
+            ("render", "--colour") => render.colouring = match value.as_str() {
+                "agent" => Colouring::ByAgent,
+                "visits" => Colouring::ByVisits,
+                _ => usage(&format!("invalid value for {}: {}", arg, value))
+            },
+            ("render", "--scale") => render.scale = parse_value(arg, value),
+            ("render", "--output") => render.output = Some(value.clone()),
+            ("animate", "--every") => every = parse_value(arg, value),
+            ("animate", "--format") => format = parse_value(arg, value),
+            ("animate", "--output") => output = Some(value.clone()),
+            _ => usage(&format!("unknown argument: {}", arg))
+        }
+    }
+    let days = day.map(|d| vec![d]).unwrap_or_else(|| (1..=DAYS).collect());
+    let parts = match part {
+        Some(p @ 1..=2) => vec![p],
+        Some(p) => usage(&format!("invalid part: {}", p)),
+        None => vec![1, 2]
+    };
+    match mode {
+        "verify" => Command::Verify {
+            days,
+            answers: answers.unwrap_or_else(|| ANSWERS.to_string()),
+        },
+        "bench" => {
+            if options.runs == 0 {
+                usage("--runs must be at least 1");
+            }
+            Command::Bench { days, parts, options }
+        },
+        "generate" => Command::Generate {
+            day: day.unwrap_or_else(|| usage("missing --day")),
+            options: generate,
+        },
+        "render" => {
+            let day = day.unwrap_or_else(|| usage("missing --day"));
+            if render.santas == 0 {
+                usage("--santas must be at least 1");
+            }
+            Command::Render {
+                day,
+                input: input.unwrap_or_else(|| input_for(day)),
+                options: render,
+            }
+        },
+        "explain" => {
+            let day = day.unwrap_or_else(|| usage("missing --day"));
+            Command::Explain {
+                day,
+                parts,
+                input: input.unwrap_or_else(|| input_for(day)),
+            }
+        },
+        "classify" => {
+            let day = day.unwrap_or_else(|| usage("missing --day"));
+            Command::Classify { day, input: input.unwrap_or_else(|| input_for(day)) }
+        },
+        "animate" => {
+            let day = day.unwrap_or_else(|| usage("missing --day"));
+            if every == 0 {
+                usage("--every must be at least 1");
+            }
+            Command::Animate {
+                day,
+                part: part.unwrap_or(2),
+                input: input.unwrap_or_else(|| input_for(day)),
+                options: AnimateOptions {
+                    every,
+                    format,
+                    output: output.unwrap_or_else(|| usage("missing --output")),
+                },
+            }
+        },
+        _ => {
+            let day = day.unwrap_or_else(|| usage("missing --day"));
+            Command::Run {
+                day,
+                parts,
+                input: input.unwrap_or_else(|| input_for(day)),
+            }
+        }
+    }
+}
+
+fn run(day: u32, parts: &[u32], input: &str) {
+    let runner = solver(day).unwrap_or_else(|| usage(&format!("no solver for day {}", day)));
+    let data = load_string(input).unwrap_or_else(|e| fail(&e.to_string()));
+    for part in parts {
+        match runner.run(*part, &data) {
+            Ok(answer) => println!("day {} part {}: {}", day, part, answer),
+            Err(e) => fail(&format!("{}: {}", input, e))
+        }
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3}ms", d.as_secs_f64() * 1000.0)
+}
+
+/// Run every solver against its input and compare with the known answers.
+/// Returns false if any answer is wrong or its solver failed.
+fn verify(days: &[u32], answers: &str) -> bool {
+    let answers = Answers::load(answers).unwrap_or_else(|e| fail(&format!("{}: {}", answers, e)));
+    let mut ok = true;
+    println!("{:>3} {:>4}  {:<7} {:>12}  answer", "day", "part", "status", "time");
+    for day in days {
+        let runner = solver(*day).unwrap_or_else(|| usage(&format!("no solver for day {}", day)));
+        let data = load_string(&input_for(*day));
+        for part in 1..=2 {
+            let (status, time, answer) = match &data {
+                Err(e) => ("missing", None, e.to_string()),
+                Ok(data) => {
+                    let start = Instant::now();
+                    let result = runner.run(part, data);
+                    let elapsed = Some(start.elapsed());
+                    match (result, answers.get(*day, part)) {
+                        (Err(e), _) => ("error", elapsed, e.to_string()),
+                        (Ok(a), None) => ("missing", elapsed, format!("{} (no known answer)", a)),
+                        (Ok(a), Some(expected)) if a == expected => ("pass", elapsed, a),
+                        (Ok(a), Some(expected)) => ("fail", elapsed, format!("{} (expected {})", a, expected)),
+                    }
+                }
+            };
+            ok &= status != "fail" && status != "error";
+            println!("{:>3} {:>4}  {:<7} {:>12}  {}", day, part, status,
+                     time.map(format_duration).unwrap_or_default(), answer);
+        }
+    }
+    ok
+}
+
+fn format_stats(nanos: [u64; 3]) -> String {
+    let [min, median, max] = nanos;
+    format!("{} / {} / {}",
+            format_duration(Duration::from_nanos(min)),
+            format_duration(Duration::from_nanos(median)),
+            format_duration(Duration::from_nanos(max)))
+}
+
+/// Time parse and solve of every selected day and part, optionally saving the
+/// results as a baseline or comparing them against an earlier one.
+/// Returns false if any solver failed or got slower than the threshold allows.
+fn bench(days: &[u32], parts: &[u32], options: &BenchOptions) -> bool {
+    let baseline = options.baseline.as_ref()
+        .map(|b| load_baseline(b).unwrap_or_else(|e| fail(&format!("{}: {}", b, e))))
+        .unwrap_or_default();
+    let limit = 1.0 + options.threshold / 100.0;
+    let mut ok = true;
+    let mut measurements: Vec<Measurement> = Vec::new();
+    println!("{:>3} {:>4}  {:<36} {:<36} baseline", "day", "part",
+             "parse (min / median / max)", "solve (min / median / max)");
+    for day in days {
+        let runner = solver(*day).unwrap_or_else(|| usage(&format!("no solver for day {}", day)));
+        let data = match load_string(&input_for(*day)) {
+            Ok(data) => data,
+            Err(e) => {
+                println!("{:>3} {:>4}  {}", day, "", e);
+                continue;
+            }
+        };
+        for part in parts {
+            let m = match measure(runner.as_ref(), *day, *part, &data, options.runs) {
+                Ok(m) => m,
+                Err(e) => {
+                    ok = false;
+                    println!("{:>3} {:>4}  {}", day, part, e);
+                    continue;
+                }
+            };
+            let vs_baseline = match compare(&m, &baseline) {
+                Some(ratio) if ratio > limit => {
+                    ok = false;
+                    format!("{:+.1}% REGRESSION", (ratio - 1.0) * 100.0)
+                },
+                Some(ratio) => format!("{:+.1}%", (ratio - 1.0) * 100.0),
+                None => String::new()
+            };
+            println!("{:>3} {:>4}  {:<36} {:<36} {}", day, part,
+                     format_stats([m.parse.min, m.parse.median, m.parse.max]),
+                     format_stats([m.solve.min, m.solve.median, m.solve.max]),
+                     vs_baseline);
+            measurements.push(m);
+        }
+    }
+    if let Some(save) = &options.save {
+        save_baseline(save, &measurements).unwrap_or_else(|e| fail(&e.to_string()));
+    }
+    ok
+}
+
+/// Write a random puzzle input for stress testing to stdout.
+fn generate(day: u32, options: &GenerateOptions) {
+    let input = match day {
+        2 => Order::random(options.count, options.max_dim, options.seed).to_string(),
+        5 => random_strings(options.count, options.seed),
+        _ => usage(&format!("no generator for day {}", day))
+    };
+    print!("{}", input);
+}
+
+/// Draw the houses visited on day 3 as a map or image.
+fn render(day: u32, input: &str, options: &RenderOptions) {
+    if day != 3 {
+        usage(&format!("no renderer for day {}", day));
+    }
+    let data = load_string(input).unwrap_or_else(|e| fail(&e.to_string()));
+    let alphabet = Alphabet::preset(&options.alphabet)
+        .map_or_else(|| Alphabet::load(&options.alphabet), Ok)
+        .unwrap_or_else(|e| fail(&format!("{}: {}", options.alphabet, e)));
+    let mut delivery = Delivery::with_alphabet(options.santas, alphabet);
+    delivery.run(data.trim_end(), &TurnOrder::RoundRobin)
+        .unwrap_or_else(|e| fail(&format!("{}: {}", input, e)));
+    let output = options.output.as_deref().unwrap_or("-");
+    let mut w: Box<dyn Write> = match options.output {
+        Some(ref path) => Box::new(File::create(path).unwrap_or_else(|e| fail(&format!("{}: {}", path, e)))),
+        None => Box::new(io::stdout().lock())
+    };
+    let image = || delivery.render(options.colouring).scale(options.scale);
+    let result = match options.format.as_str() {
+        "ppm" => image().write_ppm(&mut w),
+        "png" => image().write_png(&mut w),
+        _ => w.write_all(delivery.heatmap().as_bytes())
+    };
+    result.and_then(|_| w.flush())
+        .unwrap_or_else(|e| fail(&format!("{}: {}", output, e)));
+}
+
+/// List every naughty string of day 5 with the rules it breaks, followed by
+/// the number of strings each rule rejected.
+fn explain(day: u32, parts: &[u32], input: &str) {
+    if day != 5 {
+        usage(&format!("no explanation for day {}", day));
+    }
+    let data = load_string(input).unwrap_or_else(|e| fail(&e.to_string()));
+    for part in parts {
+        let rules = if *part == 1 { RuleSet::nice() } else { RuleSet::really_nice() };
+        let mut nice = 0;
+        let mut rejected: Vec<(String, usize)> = Vec::new();
+        println!("part {}", part);
+        for (i, line) in data.lines().enumerate() {
+            let failures = rules.explain(line);
+            if failures.is_empty() {
+                nice += 1;
+                continue;
+            }
+            let reasons: Vec<String> = failures.iter().map(|f| f.to_string()).collect();
+            println!("{:>5}  {}  {}", i + 1, line, reasons.join("; "));
+            for failure in failures {
+                match rejected.iter_mut().find(|(rule, _)| *rule == failure.rule) {
+                    Some((_, count)) => *count += 1,
+                    None => rejected.push((failure.rule, 1))
+                }
+            }
+        }
+        println!("{} nice, {} naughty", nice, data.lines().count() - nice);
+        for (rule, count) in rejected {
+            println!("{:>5}  {}", count, rule);
+        }
+    }
+}
+
+/// Count the nice strings of day 5 in a stream of any size, reporting throughput.
+fn classify_stream(day: u32, input: &str) {
+    if day != 5 {
+        usage(&format!("no classifier for day {}", day));
+    }
+    let mut reader = input::reader(input).unwrap_or_else(|e| fail(&e.to_string()));
+    let start = Instant::now();
+    let totals = classify(reader.as_mut()).unwrap_or_else(|e| fail(&format!("{}: {}", input, e)));
+    let elapsed = start.elapsed();
+    let secs = elapsed.as_secs_f64().max(1e-9);
+    println!("{} lines: {} nice, {} really nice", totals.lines, totals.nice, totals.really_nice);
+    println!("{} bytes in {}: {:.2}M lines/s, {:.1} MB/s", totals.bytes, format_duration(elapsed),
+             totals.lines as f64 / secs / 1e6, totals.bytes as f64 / secs / 1e6);
+}
+
+/// Export the day 6 light grid after every Nth instruction.
+fn animate_lights(day: u32, part: u32, input: &str, options: &AnimateOptions) {
+    if day != 6 {
+        usage(&format!("no animation for day {}", day));
+    }
+    let data = load_string(input).unwrap_or_else(|e| fail(&e.to_string()));
+    let instructions = day6::parse(&data).unwrap_or_else(|e| fail(&format!("{}: {}", input, e)));
+    let frames = match part {
+        1 => animate(OnOff, &instructions, options.every, options.format, &options.output),
+        _ => animate(Brightness, &instructions, options.every, options.format, &options.output)
+    };
+    match frames {
+        Ok(frames) => println!("{} frames written to {}", frames, options.output),
+        Err(e) => fail(&format!("{}: {}", input, e))
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match parse_args(&args) {
+        Command::Run { day, parts, input } => run(day, &parts, &input),
+        Command::Verify { days, answers } => if !verify(&days, &answers) {
+            process::exit(1);
+        },
+        Command::Bench { days, parts, options } => if !bench(&days, &parts, &options) {
+            process::exit(1);
+        },
+        Command::Generate { day, options } => generate(day, &options),
+        Command::Render { day, input, options } => render(day, &input, &options),
+        Command::Explain { day, parts, input } => explain(day, &parts, &input),
+        Command::Classify { day, input } => classify_stream(day, &input),
+        Command::Animate { day, part, input, options } => animate_lights(day, part, &input, &options),
+    }
+}
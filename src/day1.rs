@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::AocError;
+use crate::solver::Solver;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+/// How to treat characters other than `(` and `)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unknown {
+    /// Go down, as the original puzzle solution did.
+    Down,
+    /// Reject them all.
+    Error,
+    /// Skip whitespace, reject anything else.
+    SkipWhitespace,
+}
+
+/// Characters that are not a direction, with their (0-based) offset.
+#[derive(Debug, PartialEq)]
+pub struct InvalidDirections {
+    pub invalid: Vec<(usize, char)>,
+}
+
+impl Display for InvalidDirections {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let invalid: Vec<String> = self.invalid.iter()
+            .map(|(offset, c)| format!("{:?} at offset {}", c, offset))
+            .collect();
+        write!(f, "invalid directions: {}", invalid.join(", "))
+    }
+}
+
+impl Error for InvalidDirections {}
+
+impl From<InvalidDirections> for AocError {
+    fn from(e: InvalidDirections) -> Self {
+        // the message already lists the characters
+        AocError::parse("", e)
+    }
+}
+
+/// Validate the directions, collecting every character that is not one.
+pub fn parse_directions(directions: &str, unknown: Unknown) -> Result<Vec<Direction>, InvalidDirections> {
+    let mut parsed = Vec::with_capacity(directions.len());
+    let mut invalid = Vec::new();
+    for (offset, c) in directions.chars().enumerate() {
+        match (c, unknown) {
+            ('(', _) => parsed.push(Direction::Up),
+            (')', _) | (_, Unknown::Down) => parsed.push(Direction::Down),
+            (_, Unknown::SkipWhitespace) if c.is_whitespace() => {},
+            _ => invalid.push((offset, c))
+        }
+    }
+    if invalid.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(InvalidDirections { invalid })
+    }
+}
+
+/// Every floor visited while following the directions.
+#[derive(Debug)]
+pub struct FloorTrace {
+    /// Floor after each step, so `floors[0]` is the floor at position 1.
+    floors: Vec<i32>,
+}
+
+impl FloorTrace {
+    pub fn new(directions: &str, unknown: Unknown) -> Result<FloorTrace, InvalidDirections> {
+        Ok(FloorTrace::from_directions(&parse_directions(directions, unknown)?))
+    }
+
+    pub fn from_directions(directions: &[Direction]) -> FloorTrace {
+        let mut floor = 0;
+        let floors = directions.iter()
+            .map(|d| {
+                floor += if *d == Direction::Up { 1 } else { -1 };
+                floor
+            })
+            .collect();
+        FloorTrace { floors }
+    }
+
+    pub fn floors(&self) -> &[i32] {
+        &self.floors
+    }
+
+    /// Floor reached at the end.
+    pub fn floor(&self) -> i32 {
+        self.floors.last().copied().unwrap_or(0)
+    }
+
+    /// Lowest floor reached, counting the ground floor we start on.
+    pub fn min(&self) -> i32 {
+        self.floors.iter().copied().fold(0, i32::min)
+    }
+
+    /// Highest floor reached, counting the ground floor we start on.
+    pub fn max(&self) -> i32 {
+        self.floors.iter().copied().fold(0, i32::max)
+    }
+
+    /// Position at which `floor` is entered for the first time, 0 for the ground floor.
+    pub fn first_entered(&self, floor: i32) -> Option<usize> {
+        if floor == 0 {
+            return Some(0);
+        }
+        self.floors.iter()
+            .position(|f| *f == floor)
+            .map(|i| i + 1)
+    }
+
+    /// Position at which each floor is entered for the first time.
+    pub fn first_entries(&self) -> BTreeMap<i32, usize> {
+        let mut entries = BTreeMap::new();
+        entries.insert(0, 0);
+        for (i, f) in self.floors.iter().enumerate() {
+            entries.entry(*f).or_insert(i + 1);
+        }
+        entries
+    }
+
+    /// Number of times the basement is entered from the ground floor.
+    pub fn basement_visits(&self) -> usize {
+        let mut prev = 0;
+        let mut visits = 0;
+        for f in &self.floors {
+            if prev == 0 && *f < 0 {
+                visits += 1;
+            }
+            prev = *f;
+        }
+        visits
+    }
+}
+
+pub fn floor(directions: &str) -> (i32, Option<i32>) {
+    let trace = FloorTrace::new(directions, Unknown::Down).expect("unknown directions go down");
+    (trace.floor(), trace.first_entered(-1).map(|pos| pos as i32))
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = FloorTrace;
+
+    fn parse(&self, input: &str) -> Result<FloorTrace, AocError> {
+        let directions = parse_directions(input.trim_end_matches(&['\r', '\n'][..]), Unknown::Error)?;
+        Ok(FloorTrace::from_directions(&directions))
+    }
+
+    fn part1(&self, trace: &FloorTrace) -> Result<String, AocError> {
+        Ok(trace.floor().to_string())
+    }
+
+    fn part2(&self, trace: &FloorTrace) -> Result<String, AocError> {
+        trace.first_entered(-1)
+            .map(|pos| pos.to_string())
+            .ok_or_else(|| AocError::NoSolution("never enters the basement".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Runner;
+
+    #[test]
+    fn test_floor() {
+        assert_eq!(floor("(())"), (0, None));
+        assert_eq!(floor("()()"), (0, None));
+        assert_eq!(floor("((("), (3, None));
+        assert_eq!(floor("(()(()("), (3, None));
+        assert_eq!(floor("))((((("), (3, Some(1)));
+        assert_eq!(floor("())"), (-1, Some(3)));
+        assert_eq!(floor("))("), (-1, Some(1)));
+        assert_eq!(floor(")))"), (-3, Some(1)));
+        assert_eq!(floor(")())())"), (-3, Some(1)));
+    }
+
+    #[test]
+    fn test_basement() {
+        assert_eq!(floor(")"), (-1, Some(1)));
+        assert_eq!(floor("()())"), (-1, Some(5)));
+    }
+
+    #[test]
+    fn test_trace() {
+        let trace = FloorTrace::new("(()))())((", Unknown::Error).unwrap();
+        assert_eq!(trace.floors(), [1, 2, 1, 0, -1, 0, -1, -2, -1, 0]);
+        assert_eq!(trace.floor(), 0);
+        assert_eq!((trace.min(), trace.max()), (-2, 2));
+        assert_eq!(trace.first_entered(2), Some(2));
+        assert_eq!(trace.first_entered(-2), Some(8));
+        assert_eq!(trace.first_entered(3), None);
+        assert_eq!(trace.basement_visits(), 2);
+        let entries: Vec<(i32, usize)> = trace.first_entries().into_iter().collect();
+        assert_eq!(entries, [(-2, 8), (-1, 5), (0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_unknown() {
+        assert_eq!(FloorTrace::new("((x", Unknown::Down).unwrap().floor(), 1);
+        assert!(FloorTrace::new("((x", Unknown::Error).is_err());
+    }
+
+    #[test]
+    fn test_invalid_directions() {
+        assert_eq!(parse_directions("(( )\nx", Unknown::Error),
+                   Err(InvalidDirections { invalid: vec![(2, ' '), (4, '\n'), (5, 'x')] }));
+        assert_eq!(parse_directions("(( )\nx", Unknown::SkipWhitespace),
+                   Err(InvalidDirections { invalid: vec![(5, 'x')] }));
+        assert_eq!(parse_directions("(\t)\n", Unknown::SkipWhitespace),
+                   Ok(vec![Direction::Up, Direction::Down]));
+    }
+
+    #[test]
+    fn test_parse_trailing_newline() {
+        assert_eq!(Day1.run(1, "(()\n").unwrap(), "1");
+        assert!(Day1.run(2, "(()\n").is_err());
+        assert_eq!(Day1.run(2, "())\n").unwrap(), "3");
+        let e = Day1.run(1, "( ()\n").unwrap_err();
+        assert_eq!(e.to_string(), "invalid directions: ' ' at offset 1");
+    }
+}
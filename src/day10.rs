@@ -0,0 +1,107 @@
+use crate::AocError;
+use crate::solver::Solver;
+
+pub fn look_and_say_vec(input: &[u32]) -> Vec<u32> {
+    let mut output: Vec<u32> = Vec::new();
+    let mut num: u32 = 0;
+    let mut prev: Option<u32> = None;
+    for n in input {
+        match prev {
+            Some(p) =>
+                if p == *n {
+                    num += 1;
+                } else {
+                    output.push(num);
+                    output.push(p);
+                    prev = Some(*n);
+                    num = 1;
+                },
+            None => {
+                prev = Some(*n);
+                num = 1;
+            }
+        }
+    }
+    if let Some(p) = prev {
+        output.push(num);
+        output.push(p);
+    }
+    output
+}
+
+pub fn to_vec(digits: &str) -> Result<Vec<u32>, AocError> {
+    digits.chars()
+        .map(|c| c.to_digit(10).ok_or_else(|| AocError::parse(digits, format!("not a digit: {:?}", c))))
+        .collect()
+}
+
+pub fn from_vec(digits: &[u32]) -> String {
+    digits.iter()
+        .map(|i| i.to_string())
+        .fold("".to_string(), |acc, s| acc + s.as_str())
+}
+
+pub fn look_and_say(digits: &str) -> Result<String, AocError> {
+    let input = to_vec(digits)?;
+    let output = look_and_say_vec(&input);
+    Ok(from_vec(&output))
+}
+
+pub fn look_and_say_loop(digits: &str, count: usize) -> Result<String, AocError> {
+    let mut input = to_vec(digits)?;
+    for _ in 0..count {
+        input = look_and_say_vec(&input);
+    }
+    Ok(from_vec(&input))
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, AocError> {
+        let digits = input.trim();
+        if digits.is_empty() {
+            return Err(AocError::parse(digits, "expected a sequence of digits"));
+        }
+        to_vec(digits)?;
+        Ok(digits.to_string())
+    }
+
+    fn part1(&self, digits: &String) -> Result<String, AocError> {
+        Ok(look_and_say_loop(digits, 40)?.len().to_string())
+    }
+
+    fn part2(&self, digits: &String) -> Result<String, AocError> {
+        Ok(look_and_say_loop(digits, 50)?.len().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_look_and_say() {
+        assert_eq!(look_and_say("1").unwrap(), "11");
+        assert_eq!(look_and_say("11").unwrap(), "21");
+        assert_eq!(look_and_say("21").unwrap(), "1211");
+        assert_eq!(look_and_say("1211").unwrap(), "111221");
+        assert_eq!(look_and_say("111221").unwrap(), "312211");
+    }
+
+    #[test]
+    fn test_look_and_say_loop() {
+        assert_eq!(look_and_say_loop("1", 5).unwrap(), "312211");
+    }
+
+    #[test]
+    fn test_not_digits() {
+        assert!(look_and_say("12a").is_err());
+        assert_eq!(look_and_say("").unwrap(), "");
+        assert!(Day10.parse("\n").is_err());
+        assert!(Day10.parse("1-2").is_err());
+        assert_eq!(Day10.parse("123\n").unwrap(), "123");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::AocError;
+use crate::solver::Solver;
+use std::collections::HashSet;
+
+fn is_forbidden(ch: u8) -> bool {
+    matches!(ch as char, 'i' | 'o' | 'l')
+}
+
+pub fn is_valid(password: &[u8]) -> bool {
+    // continuous straigth of 3
+    let mut prev: u8 = 0;
+    let mut straight = 0;
+    // no i, o or l
+    let mut has_forbidden = false;
+    // two different non-overlapping pairs
+    let mut pairs = HashSet::new();
+
+    for c in password {
+        if straight < 3 {
+            if prev + 1 == *c {
+                straight += 1;
+            } else {
+                straight = 1;
+            }
+        }
+        has_forbidden |= is_forbidden(*c);
+        if prev == *c {
+            pairs.insert(*c);
+        }
+        prev = *c;
+    }
+    straight == 3 && !has_forbidden && pairs.len() >= 2
+}
+
+pub fn next_char(ch: u8) -> (u8, bool) {
+    let mut next = ch;
+    loop {
+        let (n, w) = match next {
+            b'z' => (b'a', true),
+            _ => (next + 1, false)
+        };
+        // optimize, ski forbidden characters
+        if !is_forbidden(n) {
+            return (n, w)
+        }
+        next = n;
+    }
+}
+
+fn next_word(word: &mut [u8]) {
+    let last = word.len() - 1;
+    let (n, wrapped) = next_char(word[last]);
+//    println!("{}: {} -> {}, {}", last, word[last], n, wrapped);
+    word[last] = n;
+    if wrapped {
+        for i in (0..last).rev() {
+            let (n, wrapped) = next_char(word[i]);
+//            println!("{}: {} -> {}, {}", i, word[i], n, wrapped);
+            word[i] = n;
+            if !wrapped {
+                break;
+            }
+        }
+    }
+}
+
+pub fn next_password(password: &[u8]) -> Vec<u8> {
+    let mut next = password.to_vec();
+    loop {
+        next_word(&mut next);
+//        println!("{:?}", String::from_utf8_lossy(&next));
+        if is_valid(&next) {
+            break;
+        }
+    }
+    next
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Vec<u8>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u8>, AocError> {
+        let password = input.trim();
+        if !password.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err(AocError::parse(password, "expected lowercase letters"));
+        }
+        // shorter passwords can never hold a straight and two pairs, so the search would not end
+        if password.len() < 5 {
+            return Err(AocError::parse(password, "expected at least 5 letters"));
+        }
+        Ok(password.as_bytes().to_vec())
+    }
+
+    fn part1(&self, password: &Vec<u8>) -> Result<String, AocError> {
+        Ok(String::from_utf8_lossy(&next_password(password)).to_string())
+    }
+
+    fn part2(&self, password: &Vec<u8>) -> Result<String, AocError> {
+        let next = next_password(password);
+        Ok(String::from_utf8_lossy(&next_password(&next)).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_char() {
+        assert_eq!(next_char(b'a'), (b'b', false));
+        assert_eq!(next_char(b'h'), (b'j', false));
+        assert_eq!(next_char(b'z'), (b'a', true));
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(!is_valid(b"hijklmmn"));
+        assert!(!is_valid(b"abbceffg"));
+        assert!(!is_valid(b"abbcegjk"));
+        assert!(!is_valid(b"abcccc"));
+        assert!(is_valid(b"abccbb"));
+    }
+
+    #[test]
+    fn test_next_password() {
+        assert_eq!(next_password(b"abcdefgh"), b"abcdffaa");
+        assert_eq!(next_password(b"ghijklmn"), b"ghjaabcc");
+    }
+
+    #[test]
+    fn test_not_lowercase() {
+        assert!(Day11.parse("\n").is_err());
+        assert!(Day11.parse("ABCDEFGH").is_err());
+        assert!(Day11.parse("garbage line").is_err());
+        assert!(Day11.parse("abcd").is_err());
+        assert_eq!(Day11.parse("abcdefgh\n").unwrap(), b"abcdefgh");
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt::{Debug, Write, Formatter, Error};
+use std::iter::Peekable;
+use std::str::CharIndices;
+use crate::AocError;
+use crate::solver::Solver;
+
+#[derive(Debug)]
+pub enum TokenType {
+    ObjectStart,
+    ObjectEnd,
+    ArrayStart,
+    ArrayEnd,
+    Number,
+    String
+}
+
+pub union Value<'a> {
+    num: i32,
+    str: &'a str,
+}
+
+impl<'a> Value<'a> {
+    fn num(&self) -> i32 {
+        unsafe { self.num }
+    }
+
+    fn str(&self) -> &'a str {
+        unsafe { self.str }
+    }
+}
+
+pub struct Token<'a> {
+    tp: TokenType,
+    value: Option<Value<'a>>,
+}
+
+impl<'a> Token<'a> {
+    fn simple(tp: TokenType) -> Self {
+        Token { tp, value: None }
+    }
+
+    fn string(s: &'a str) -> Self {
+        Token {
+            tp: TokenType::String,
+            value: Some(Value { str: s }),
+        }
+    }
+
+    fn number(n: i32) -> Self {
+        Token {
+            tp: TokenType::Number,
+            value: Some(Value { num: n }),
+        }
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(self.tp, TokenType::Number)
+    }
+}
+
+impl<'a> Debug for Token<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match &self.tp {
+            TokenType::ObjectStart => f.write_char('{'),
+            TokenType::ObjectEnd => f.write_char('}'),
+            TokenType::ArrayStart => f.write_char('['),
+            TokenType::ArrayEnd => f.write_char(']'),
+            TokenType::Number => f.write_fmt(format_args!("Num: {}", self.value.as_ref().unwrap().num())),
+            TokenType::String => f.write_fmt(format_args!("Str: '{}'", self.value.as_ref().unwrap().str())),
+        }
+    }
+}
+
+pub struct JsonIter<'a> {
+    str: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> JsonIter<'a> {
+    pub fn new(s: &'a str) -> Self {
+        JsonIter {
+            str: s,
+            chars: s.char_indices().peekable(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.chars = self.str.char_indices().peekable();
+    }
+
+    fn eat_literal(&mut self, start: usize) -> &'a str {
+        let mut end = start + 1;
+        while let Some((_, 'a'..='z')) = self.chars.peek() {
+            self.chars.next();
+            end += 1;
+        }
+        &self.str[start..end]
+    }
+
+    fn eat_string(&mut self, start: usize) -> Result<&'a str, AocError> {
+        let mut escaped = false;
+        for (i, ch) in &mut self.chars {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => return Ok(&self.str[start + 1..i]),
+                _ => {}
+            }
+        }
+        let string: String = self.str[start..].chars().take(16).collect();
+        Err(AocError::parse(&string, format!("unterminated string at offset {}", start)))
+    }
+
+    fn eat_number(&mut self, start: usize) -> Result<i32, AocError> {
+        let mut end = start + 1;
+        while let Some((_, '0'..='9')) = self.chars.peek() {
+            self.chars.next();
+            end += 1;
+        }
+        let number = &self.str[start..end];
+        number.parse::<i32>().map_err(|e| AocError::parse(number, e))
+    }
+}
+
+impl<'a> Iterator for JsonIter<'a> {
+    type Item = Result<Token<'a>, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.chars.next() {
+            Some((i, ch)) => match ch {
+                '{' => Some(Ok(Token::simple(TokenType::ObjectStart))),
+                '}' => Some(Ok(Token::simple(TokenType::ObjectEnd))),
+                '[' => Some(Ok(Token::simple(TokenType::ArrayStart))),
+                ']' => Some(Ok(Token::simple(TokenType::ArrayEnd))),
+                '"' => Some(self.eat_string(i).map(Token::string)),
+                'a'..='z' => Some(Ok(Token::string(self.eat_literal(i)))),
+                '-' | '0'..='9' => Some(self.eat_number(i).map(Token::number)),
+                ':' | ',' => self.next(), // skip
+                _ if ch.is_whitespace() => self.next(),
+                _ => Some(Err(AocError::parse(&ch.to_string(), format!("unexpected character at offset {}", i))))
+            },
+            None => None
+        }
+    }
+}
+
+pub fn sum_numbers(json: &mut JsonIter) -> Result<i32, AocError> {
+    let mut sum = 0;
+    for token in json {
+        let token = token?;
+        if token.is_number() {
+            sum += token.value.unwrap().num()
+        }
+    }
+    Ok(sum)
+}
+
+pub fn sum_except_red(json: &mut JsonIter) -> Result<i32, AocError> {
+    fn recurse(json: &mut JsonIter) -> Result<i32, AocError> {
+        let mut has_red = false;
+        let mut in_array = 0;
+        let mut sum = 0;
+        while let Some(token) = json.next() {
+            let token = token?;
+            match token.tp {
+                TokenType::Number => sum += token.value.unwrap().num(),
+                TokenType::ObjectStart => sum += recurse(json)?,
+                TokenType::ObjectEnd => break,
+                TokenType::ArrayStart=> in_array += 1,
+                TokenType::ArrayEnd=> in_array -= 1,
+                TokenType::String => has_red |= in_array == 0 && token.value.unwrap().str() == "red",
+            }
+        }
+        Ok(if has_red { 0 } else { sum })
+    }
+    recurse(json)
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, AocError> {
+        Ok(input.trim_end().to_string())
+    }
+
+    fn part1(&self, data: &String) -> Result<String, AocError> {
+        Ok(sum_numbers(&mut JsonIter::new(data))?.to_string())
+    }
+
+    fn part2(&self, data: &String) -> Result<String, AocError> {
+        Ok(sum_except_red(&mut JsonIter::new(data))?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_numbers() {
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"[1,2,3]"#)).unwrap(), 6);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"{"a":2,"b":4}"#)).unwrap(), 6);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"[[[3]]]"#)).unwrap(), 3);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"{"a":{"b":4},"c":-1}"#)).unwrap(), 3);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"{"a":[-1,1]}"#)).unwrap(), 0);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"[-1,{"a":1}]"#)).unwrap(), 0);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"[]"#)).unwrap(), 0);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"{}"#)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_sum_except_red() {
+        assert_eq!(sum_except_red(&mut JsonIter::new(r#"[1,2,3]"#)).unwrap(), 6);
+        assert_eq!(sum_except_red(&mut JsonIter::new(r#"[1,{"c":"red","b":2},3]"#)).unwrap(), 4);
+        assert_eq!(sum_except_red(&mut JsonIter::new(r#"{"d":"red","e":[1,2,3,4],"f":5}"#)).unwrap(), 0);
+        assert_eq!(sum_except_red(&mut JsonIter::new(r#"[1,"red",5]"#)).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_whitespace_and_strings() {
+        let json = "{\n  \"a\": [1, 2],\n  \"b\": \"Red, \\\"3\\\"\"\n}\n";
+        assert_eq!(sum_numbers(&mut JsonIter::new(json)).unwrap(), 3);
+        assert_eq!(sum_except_red(&mut JsonIter::new(r#"{"a": "red", "b": 1}"#)).unwrap(), 0);
+        assert_eq!(sum_numbers(&mut JsonIter::new(r#"["é", 4]"#)).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(sum_numbers(&mut JsonIter::new("[1, #]")).is_err());
+        assert!(sum_numbers(&mut JsonIter::new(r#"["a"#)).is_err());
+        assert!(sum_numbers(&mut JsonIter::new("[-]")).is_err());
+        assert!(sum_numbers(&mut JsonIter::new("[99999999999]")).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use std::cmp::min;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::AocError;
+use crate::solver::Solver;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Present {
+    l: u32,
+    w: u32,
+    h: u32,
+}
+
+impl Present {
+    pub fn new(l: u32, w: u32, h: u32) -> Result<Present, AocError> {
+        if l == 0 || w == 0 || h == 0 {
+            return Err(AocError::parse(&format!("{}x{}x{}", l, w, h), "dimensions must be positive"));
+        }
+        Ok(Present { l, w, h })
+    }
+
+    pub fn l(&self) -> u32 {
+        self.l
+    }
+
+    pub fn w(&self) -> u32 {
+        self.w
+    }
+
+    pub fn h(&self) -> u32 {
+        self.h
+    }
+
+    /// Dimensions widened to u64: products of two always fit, products of three may not,
+    /// hence the `None`s on overflow.
+    fn dims(&self) -> (u64, u64, u64) {
+        (self.l as u64, self.w as u64, self.h as u64)
+    }
+
+    pub fn surface_area(&self) -> Option<u64> {
+        let (l, w, h) = self.dims();
+        (l * w).checked_add(w * h)?.checked_add(h * l)?.checked_mul(2)
+    }
+
+    /// Area of the smallest side.
+    pub fn smallest_side(&self) -> u64 {
+        let (l, w, h) = self.dims();
+        min(l * w, min(w * h, h * l))
+    }
+
+    pub fn smallest_perimeter(&self) -> u64 {
+        let (l, w, h) = self.dims();
+        2 * min(l + w, min(w + h, h + l))
+    }
+
+    pub fn volume(&self) -> Option<u64> {
+        let (l, w, h) = self.dims();
+        (l * w).checked_mul(h)
+    }
+
+    pub fn wrapping_paper(&self) -> Option<u64> {
+        self.surface_area()?.checked_add(self.smallest_side())
+    }
+
+    pub fn ribbon(&self) -> Option<u64> {
+        self.volume()?.checked_add(self.smallest_perimeter())
+    }
+
+    /// Volume that never overflows, for comparing presents.
+    fn exact_volume(&self) -> u128 {
+        self.l as u128 * self.w as u128 * self.h as u128
+    }
+}
+
+impl Display for Present {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}x{}", self.l, self.w, self.h)
+    }
+}
+
+impl FromStr for Present {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dims: Vec<u32> = s.split('x')
+            .map(|d| d.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|e| AocError::parse(s, e))?;
+        match dims[..] {
+            [l, w, h] => Present::new(l, w, h),
+            _ => Err(AocError::parse(s, format!("expected 3 dimensions, got {}", dims.len())))
+        }
+    }
+}
+
+/// All presents on the list.
+#[derive(Debug)]
+pub struct Order {
+    presents: Vec<Present>,
+}
+
+impl Order {
+    pub fn parse(input: &str) -> Result<Order, AocError> {
+        let presents = input.lines()
+            .enumerate()
+            .map(|(i, s)| s.parse().map_err(|e: AocError| e.at_line(i + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok(Order { presents })
+    }
+
+    pub fn presents(&self) -> &[Present] {
+        &self.presents
+    }
+
+    /// Random order of `count` presents with dimensions up to `max_dim`, the
+    /// same for the same `seed`.
+    pub fn random(count: usize, max_dim: u32, seed: u64) -> Order {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let max_dim = max_dim.max(1);
+        let presents = (0..count)
+            .map(|_| Present {
+                l: rng.gen_range(1..=max_dim),
+                w: rng.gen_range(1..=max_dim),
+                h: rng.gen_range(1..=max_dim),
+            })
+            .collect();
+        Order { presents }
+    }
+
+    fn total(&self, what: &str, need: &dyn Fn(&Present) -> Option<u64>) -> Result<u64, AocError> {
+        self.presents.iter()
+            .enumerate()
+            .try_fold(0u64, |total, (i, p)| need(p).and_then(|n| total.checked_add(n))
+                .ok_or_else(|| AocError::Overflow(format!("total {} at present {} ({})", what, i + 1, p))))
+    }
+
+    pub fn total_paper(&self) -> Result<u64, AocError> {
+        self.total("wrapping paper", &Present::wrapping_paper)
+    }
+
+    pub fn total_ribbon(&self) -> Result<u64, AocError> {
+        self.total("ribbon", &Present::ribbon)
+    }
+
+    /// The present with the largest volume, the first one in case of a tie.
+    pub fn largest(&self) -> Option<&Present> {
+        self.presents.iter()
+            .rev()
+            .max_by_key(|p| p.exact_volume())
+    }
+
+    /// Number of presents per volume range of `bucket` wide, keyed by the start
+    /// of the range. Volumes that overflow count towards the last range.
+    pub fn histogram(&self, bucket: u64) -> BTreeMap<u64, usize> {
+        let bucket = bucket.max(1);
+        let mut histogram = BTreeMap::new();
+        for p in &self.presents {
+            let volume = p.volume().unwrap_or(u64::MAX);
+            *histogram.entry(volume / bucket * bucket).or_insert(0) += 1;
+        }
+        histogram
+    }
+}
+
+impl Display for Order {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for p in &self.presents {
+            writeln!(f, "{}", p)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Order;
+
+    fn parse(&self, input: &str) -> Result<Order, AocError> {
+        Order::parse(input)
+    }
+
+    fn part1(&self, order: &Order) -> Result<String, AocError> {
+        Ok(order.total_paper()?.to_string())
+    }
+
+    fn part2(&self, order: &Order) -> Result<String, AocError> {
+        Ok(order.total_ribbon()?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn present(s: &str) -> Present {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_wrapping_paper() {
+        assert_eq!(present("2x3x4").wrapping_paper(), Some(58));
+        assert_eq!(present("1x1x10").wrapping_paper(), Some(43));
+    }
+
+    #[test]
+    fn test_ribbon() {
+        assert_eq!(present("2x3x4").ribbon(), Some(34));
+        assert_eq!(present("1x1x10").ribbon(), Some(14));
+    }
+
+    #[test]
+    fn test_geometry() {
+        let p = present("2x3x4");
+        assert_eq!(p.surface_area(), Some(52));
+        assert_eq!(p.smallest_side(), 6);
+        assert_eq!(p.smallest_perimeter(), 10);
+        assert_eq!(p.volume(), Some(24));
+    }
+
+    #[test]
+    fn test_invalid_dimensions() {
+        assert!("2x3".parse::<Present>().is_err());
+        assert!("2x3xa".parse::<Present>().is_err());
+        assert!("2x0x4".parse::<Present>().is_err());
+        assert!("2x3x4x5".parse::<Present>().is_err());
+        assert!(Present::new(2, 3, 0).is_err());
+        assert_eq!(Present::new(2, 3, 4).unwrap(), present("2x3x4"));
+    }
+
+    #[test]
+    fn test_order() {
+        let order = Order::parse("2x3x4\n1x1x10\n4x3x2\n").unwrap();
+        assert_eq!(order.total_paper().unwrap(), 58 + 43 + 58);
+        assert_eq!(order.total_ribbon().unwrap(), 34 + 14 + 34);
+        assert_eq!(order.largest(), Some(&present("2x3x4")));
+        let histogram: Vec<(u64, usize)> = order.histogram(10).into_iter().collect();
+        assert_eq!(histogram, [(10, 1), (20, 2)]);
+        assert!(Order::parse("2x3x4\n2x3\n").is_err());
+    }
+
+    #[test]
+    fn test_large_dimensions() {
+        let p = present("4294967295x4294967295x1");
+        assert_eq!(p.wrapping_paper(), None);
+        assert_eq!(p.smallest_side(), 4_294_967_295);
+        assert_eq!(p.ribbon(), None);
+        assert_eq!(present("4294967295x1x1").ribbon(), Some(4_294_967_299));
+        assert_eq!(present("3000000x3000000x3000000").volume(), None);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let order = Order::parse("1x1x1\n2500000x2500000x2500000\n2500000x2500000x2500000\n").unwrap();
+        assert_eq!(order.total_paper().unwrap(), 7 + 2 * 43_750_000_000_000);
+        match order.total_ribbon() {
+            Err(AocError::Overflow(what)) => assert!(what.contains("present 3")),
+            r => panic!("expected overflow, got {:?}", r)
+        }
+        let histogram: Vec<(u64, usize)> = order.histogram(1_000_000).into_iter().collect();
+        assert_eq!(histogram, [(0, 1), (15_625_000_000_000_000_000, 2)]);
+    }
+
+    #[test]
+    fn test_random() {
+        let order = Order::random(1000, 30, 42);
+        assert_eq!(order.presents.len(), 1000);
+        assert!(order.presents().iter().all(|p| p.l() <= 30 && p.w() <= 30 && p.h() <= 30));
+        assert_eq!(Order::parse(&order.to_string()).unwrap().presents, order.presents);
+        assert_eq!(Order::random(1000, 30, 42).presents, order.presents);
+    }
+}
@@ -0,0 +1,449 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::{load_string, AocError};
+use crate::image::{Image, Rgb, WHITE};
+use crate::solver::Solver;
+
+pub type Pos = (i32, i32);
+
+/// A symbol in the directions that the alphabet has no move for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnknownSymbol {
+    pub symbol: char,
+    /// Offset of the symbol in the directions it was read from.
+    pub offset: usize,
+}
+
+impl Display for UnknownSymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown direction {:?} at offset {}", self.symbol, self.offset)
+    }
+}
+
+impl Error for UnknownSymbol {}
+
+impl From<UnknownSymbol> for AocError {
+    fn from(e: UnknownSymbol) -> Self {
+        // the message already names the symbol
+        AocError::parse("", e)
+    }
+}
+
+/// The moves each direction symbol stands for, with `y` growing southwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alphabet {
+    moves: HashMap<char, Pos>,
+}
+
+impl Alphabet {
+    pub fn new(moves: &[(char, Pos)]) -> Alphabet {
+        Alphabet { moves: moves.iter().copied().collect() }
+    }
+
+    /// The puzzle's `<>^v`.
+    pub fn arrows() -> Alphabet {
+        Alphabet::new(&[('<', (-1, 0)), ('>', (1, 0)), ('^', (0, -1)), ('v', (0, 1))])
+    }
+
+    /// `NSEW` compass letters.
+    pub fn compass() -> Alphabet {
+        Alphabet::new(&[('W', (-1, 0)), ('E', (1, 0)), ('N', (0, -1)), ('S', (0, 1))])
+    }
+
+    /// Numeric keypad digits, including the diagonals: `8` is north, `9` north-east, ...
+    pub fn keypad() -> Alphabet {
+        Alphabet::new(&[
+            ('7', (-1, -1)), ('8', (0, -1)), ('9', (1, -1)),
+            ('4', (-1, 0)), ('6', (1, 0)),
+            ('1', (-1, 1)), ('2', (0, 1)), ('3', (1, 1)),
+        ])
+    }
+
+    /// Hex grid in axial coordinates, the keys around `s` on a keyboard:
+    /// `w`/`e` north-west/east, `a`/`d` west/east, `z`/`x` south-west/east.
+    pub fn hex() -> Alphabet {
+        Alphabet::new(&[
+            ('w', (0, -1)), ('e', (1, -1)),
+            ('a', (-1, 0)), ('d', (1, 0)),
+            ('z', (-1, 1)), ('x', (0, 1)),
+        ])
+    }
+
+    pub fn preset(name: &str) -> Option<Alphabet> {
+        match name {
+            "arrows" => Some(Alphabet::arrows()),
+            "compass" => Some(Alphabet::compass()),
+            "keypad" => Some(Alphabet::keypad()),
+            "hex" => Some(Alphabet::hex()),
+            _ => None
+        }
+    }
+
+    pub fn load(filename: &str) -> Result<Alphabet, AocError> {
+        Alphabet::parse(&load_string(filename)?)
+    }
+
+    /// Alphabet from TOML with a `[dx, dy]` entry per symbol, e.g. `"^" = [0, -1]`.
+    pub fn parse(s: &str) -> Result<Alphabet, AocError> {
+        let moves: HashMap<String, (i32, i32)> = toml::from_str(s).map_err(|e| AocError::toml(s, e))?;
+        let moves = moves.into_iter()
+            .map(|(symbol, delta)| {
+                let mut chars = symbol.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok((c, delta)),
+                    _ => Err(AocError::parse(&symbol, "expected a single character"))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Alphabet { moves })
+    }
+
+    pub fn delta(&self, symbol: char) -> Option<Pos> {
+        self.moves.get(&symbol).copied()
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::arrows()
+    }
+}
+
+/// Which agent follows the next direction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TurnOrder {
+    /// One direction per agent, in turn.
+    RoundRobin,
+    /// Agent `i` follows `weights[i]` consecutive directions before the next one.
+    Weighted(Vec<usize>),
+}
+
+impl TurnOrder {
+    /// Agents in the order they take their turns, repeated until the directions run out.
+    fn sequence(&self, agents: usize) -> Result<Vec<usize>, AocError> {
+        let sequence: Vec<usize> = match self {
+            TurnOrder::RoundRobin => (0..agents).collect(),
+            TurnOrder::Weighted(weights) => {
+                if weights.len() != agents {
+                    return Err(AocError::parse(&format!("{:?}", weights),
+                                               format!("expected a weight for each of the {} agents", agents)));
+                }
+                weights.iter()
+                    .enumerate()
+                    .flat_map(|(agent, w)| std::iter::repeat_n(agent, *w))
+                    .collect()
+            }
+        };
+        if sequence.is_empty() {
+            return Err(AocError::parse(&format!("{:?}", self), "no agent gets a turn"));
+        }
+        Ok(sequence)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AgentStats {
+    pub moves: usize,
+    pub distinct_houses: usize,
+    /// Farthest Manhattan distance from the origin.
+    pub farthest: u32,
+}
+
+/// Any number of agents delivering presents, all starting at the origin.
+pub struct Delivery {
+    alphabet: Alphabet,
+    positions: Vec<Pos>,
+    visits: HashMap<Pos, usize>,
+    visited_by: Vec<HashSet<Pos>>,
+    stats: Vec<AgentStats>,
+}
+
+impl Delivery {
+    pub fn new(agents: usize) -> Delivery {
+        Delivery::with_alphabet(agents, Alphabet::default())
+    }
+
+    pub fn with_alphabet(agents: usize, alphabet: Alphabet) -> Delivery {
+        let mut visits = HashMap::new();
+        if agents > 0 {
+            visits.insert((0, 0), agents);
+        }
+        Delivery {
+            alphabet,
+            positions: vec![(0, 0); agents],
+            visits,
+            visited_by: vec![[(0, 0)].iter().copied().collect(); agents],
+            stats: vec![AgentStats { moves: 0, distinct_houses: 1, farthest: 0 }; agents],
+        }
+    }
+
+    pub fn agents(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Move `agent` one house in `direction` and deliver a present there.
+    /// An unknown direction is reported at the offset in the agent's own moves.
+    /// Private, as only `run` and `run_streams` make sure `agent` exists.
+    fn step(&mut self, agent: usize, direction: char) -> Result<(), UnknownSymbol> {
+        let (dx, dy) = self.alphabet.delta(direction)
+            .ok_or(UnknownSymbol { symbol: direction, offset: self.stats[agent].moves })?;
+        let pos = &mut self.positions[agent];
+        pos.0 += dx;
+        pos.1 += dy;
+        let pos = *pos;
+        *self.visits.entry(pos).or_insert(0) += 1;
+        self.visited_by[agent].insert(pos);
+        let stats = &mut self.stats[agent];
+        stats.moves += 1;
+        stats.distinct_houses = self.visited_by[agent].len();
+        stats.farthest = stats.farthest.max(pos.0.unsigned_abs() + pos.1.unsigned_abs());
+        Ok(())
+    }
+
+    /// Follow one sequence of directions, shared by the agents as given by `order`.
+    pub fn run(&mut self, directions: &str, order: &TurnOrder) -> Result<(), AocError> {
+        let sequence = order.sequence(self.agents())?;
+        for (offset, (c, agent)) in directions.chars().zip(sequence.iter().cycle()).enumerate() {
+            self.step(*agent, c).map_err(|e| UnknownSymbol { offset, ..e })?;
+        }
+        Ok(())
+    }
+
+    /// Every agent follows its own sequence of directions, taking turns until all are done.
+    pub fn run_streams(&mut self, streams: &[&str]) -> Result<(), AocError> {
+        if streams.len() != self.agents() {
+            return Err(AocError::parse(&format!("{} streams", streams.len()),
+                                       format!("expected one for each of the {} agents", self.agents())));
+        }
+        let mut streams: Vec<_> = streams.iter().map(|s| s.chars()).collect();
+        loop {
+            let mut moved = false;
+            for (agent, stream) in streams.iter_mut().enumerate() {
+                if let Some(c) = stream.next() {
+                    self.step(agent, c)?;
+                    moved = true;
+                }
+            }
+            if !moved {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Number of houses that got at least one present.
+    pub fn houses(&self) -> usize {
+        self.visits.len()
+    }
+
+    /// Number of presents delivered to each house.
+    pub fn visits(&self) -> &HashMap<Pos, usize> {
+        &self.visits
+    }
+
+    pub fn stats(&self) -> &[AgentStats] {
+        &self.stats
+    }
+
+    /// Top left and bottom right corner of the visited houses.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let xs = self.visits.keys().map(|p| p.0);
+        let ys = self.visits.keys().map(|p| p.1);
+        Some(((xs.clone().min()?, ys.clone().min()?), (xs.max()?, ys.max()?)))
+    }
+
+    /// Map of the visited houses, north up, with the number of presents per
+    /// house: `1`-`9`, `+` for more, `.` for none.
+    pub fn heatmap(&self) -> String {
+        let ((x0, y0), (x1, y1)) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new()
+        };
+        let mut map = String::new();
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                map.push(match self.visits.get(&(x, y)) {
+                    None => '.',
+                    Some(n @ 1..=9) => std::char::from_digit(*n as u32, 10).expect("single digit"),
+                    Some(_) => '+'
+                });
+            }
+            map.push('\n');
+        }
+        map
+    }
+
+    /// One pixel per house, north up, unvisited houses black.
+    pub fn render(&self, colouring: Colouring) -> Image {
+        let ((x0, y0), (x1, y1)) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Image::new(0, 0)
+        };
+        let max_visits = self.visits.values().copied().max().unwrap_or(1);
+        let mut image = Image::new((x1 - x0 + 1) as usize, (y1 - y0 + 1) as usize);
+        for (pos, visits) in &self.visits {
+            let colour = match colouring {
+                Colouring::ByAgent => {
+                    let mut agents = (0..self.agents()).filter(|a| self.visited_by[*a].contains(pos));
+                    match (agents.next(), agents.next()) {
+                        (Some(agent), None) => PALETTE[agent % PALETTE.len()],
+                        _ => WHITE
+                    }
+                },
+                Colouring::ByVisits => heat(*visits, max_visits)
+            };
+            image.set((pos.0 - x0) as usize, (pos.1 - y0) as usize, colour);
+        }
+        image
+    }
+}
+
+/// How `Delivery::render` colours a house.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Colouring {
+    /// A colour per agent, white for houses visited by more than one.
+    ByAgent,
+    /// From dark red for a single present to yellow for the most presents.
+    ByVisits,
+}
+
+const PALETTE: [Rgb; 6] = [
+    [230, 25, 75], [60, 180, 75], [0, 130, 200], [245, 130, 48], [145, 30, 180], [70, 240, 240]
+];
+
+fn heat(visits: usize, max_visits: usize) -> Rgb {
+    let t = if max_visits > 1 { (visits - 1) as f64 / (max_visits - 1) as f64 } else { 1.0 };
+    [(128.0 + 127.0 * t.min(0.5) * 2.0) as u8, (255.0 * (t - 0.5).max(0.0) * 2.0) as u8, 0]
+}
+
+pub fn count_houses(directions: &str, num_santas: usize) -> Result<usize, AocError> {
+    let mut delivery = Delivery::new(num_santas);
+    delivery.run(directions, &TurnOrder::RoundRobin)?;
+    Ok(delivery.houses())
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, AocError> {
+        Ok(input.trim_end().to_string())
+    }
+
+    fn part1(&self, directions: &String) -> Result<String, AocError> {
+        Ok(count_houses(directions, 1)?.to_string())
+    }
+
+    fn part2(&self, directions: &String) -> Result<String, AocError> {
+        Ok(count_houses(directions, 2)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_santa() {
+        assert_eq!(count_houses(">", 1).unwrap(), 2);
+        assert_eq!(count_houses("^>v<", 1).unwrap(), 4);
+        assert_eq!(count_houses("^v^v^v^v^v", 1).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_two_santas() {
+        assert_eq!(count_houses("^v", 2).unwrap(), 3);
+        assert_eq!(count_houses("^>v<", 2).unwrap(), 3);
+        assert_eq!(count_houses("^v^v^v^v^v", 2).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_many_santas() {
+        assert_eq!(count_houses("^^^vvv>>><<<", 3).unwrap(), 3);
+        assert_eq!(count_houses("^>v<", 4).unwrap(), 5);
+        assert_eq!(count_houses(">>", 5).unwrap(), 2);
+        assert!(count_houses("^x", 1).is_err());
+    }
+
+    #[test]
+    fn test_weighted() {
+        let mut delivery = Delivery::new(2);
+        delivery.run("^^>v<<", &TurnOrder::Weighted(vec![2, 1])).unwrap();
+        // santa: ^ ^ v <, robot: > <
+        assert_eq!(delivery.stats()[0], AgentStats { moves: 4, distinct_houses: 4, farthest: 2 });
+        assert_eq!(delivery.stats()[1], AgentStats { moves: 2, distinct_houses: 2, farthest: 1 });
+        assert_eq!(delivery.visits()[&(0, 0)], 3);
+        assert_eq!(delivery.houses(), 5);
+        assert!(Delivery::new(2).run("^", &TurnOrder::Weighted(vec![0, 0])).is_err());
+        assert!(Delivery::new(2).run("^", &TurnOrder::Weighted(vec![1])).is_err());
+    }
+
+    #[test]
+    fn test_streams() {
+        let mut delivery = Delivery::new(2);
+        delivery.run_streams(&[">>>", "<"]).unwrap();
+        assert_eq!(delivery.houses(), 5);
+        assert_eq!(delivery.stats()[0].farthest, 3);
+        assert_eq!(delivery.stats()[1].moves, 1);
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let mut delivery = Delivery::new(2);
+        delivery.run("^>v<<", &TurnOrder::RoundRobin).unwrap();
+        // santa: (0,-1) (0,0) (-1,0), robot: (1,0) (0,0)
+        assert_eq!(delivery.bounds(), Some(((-1, -1), (1, 0))));
+        assert_eq!(delivery.heatmap(), ".1.\n141\n");
+    }
+
+    #[test]
+    fn test_render() {
+        let mut delivery = Delivery::new(2);
+        delivery.run("^>", &TurnOrder::RoundRobin).unwrap();
+        let image = delivery.render(Colouring::ByAgent);
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.get(0, 0), PALETTE[0]);
+        assert_eq!(image.get(0, 1), WHITE);
+        assert_eq!(image.get(1, 1), PALETTE[1]);
+        assert_eq!(image.get(1, 0), [0, 0, 0]);
+        let image = delivery.render(Colouring::ByVisits);
+        assert_eq!(image.get(0, 1), [255, 255, 0]);
+        assert_eq!(image.get(0, 0), [128, 0, 0]);
+    }
+
+    #[test]
+    fn test_alphabets() {
+        let mut delivery = Delivery::with_alphabet(1, Alphabet::compass());
+        delivery.run("NESW", &TurnOrder::RoundRobin).unwrap();
+        assert_eq!(delivery.houses(), 4);
+        let mut delivery = Delivery::with_alphabet(1, Alphabet::keypad());
+        delivery.run("9999", &TurnOrder::RoundRobin).unwrap();
+        assert_eq!(delivery.stats()[0].farthest, 8);
+        let mut delivery = Delivery::with_alphabet(1, Alphabet::hex());
+        // a loop back to the origin
+        delivery.run("dwazxe", &TurnOrder::RoundRobin).unwrap();
+        assert_eq!(delivery.houses(), 6);
+        assert_eq!(delivery.visits()[&(0, 0)], 2);
+    }
+
+    #[test]
+    fn test_unknown_symbol() {
+        let mut delivery = Delivery::new(2);
+        match delivery.run("^v<x", &TurnOrder::RoundRobin) {
+            Err(e) => assert_eq!(e.to_string(), "unknown direction 'x' at offset 3"),
+            r => panic!("expected unknown direction, got {:?}", r)
+        }
+        let mut delivery = Delivery::new(2);
+        assert_eq!(delivery.step(1, 'N'), Err(UnknownSymbol { symbol: 'N', offset: 0 }));
+    }
+
+    #[test]
+    fn test_parse_alphabet() {
+        let alphabet = Alphabet::parse("\"<\" = [-1, 0]\n\">\" = [1, 0]\n\"^\" = [0, -1]\n\"v\" = [0, 1]\n").unwrap();
+        assert_eq!(alphabet, Alphabet::arrows());
+        assert!(Alphabet::parse("\"up\" = [0, -1]\n").is_err());
+        assert!(Alphabet::parse("\"^\" = [0]\n").is_err());
+        assert_eq!(Alphabet::preset("hex"), Some(Alphabet::hex()));
+    }
+}
@@ -0,0 +1,474 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use md5::Digest;
+use sha2::Digest as _;
+use crate::AocError;
+use crate::solver::Solver;
+
+/// Custom check whether a digest makes an AdventCoin.
+pub type Found = dyn Fn(&[u8]) -> bool + Send + Sync;
+
+/// When a digest is good enough to mint an AdventCoin.
+pub struct Difficulty(Requirement);
+
+enum Requirement {
+    /// At least this many leading zeros in hexadecimal.
+    ZeroNibbles(u32),
+    /// At least this many leading zero bits.
+    ZeroBits(u32),
+    /// The hexadecimal digest starts with these (lowercase) digits.
+    HexPrefix(String),
+    Predicate(Box<Found>),
+}
+
+impl Difficulty {
+    pub fn zero_nibbles(count: u32) -> Difficulty {
+        Difficulty(Requirement::ZeroNibbles(count))
+    }
+
+    pub fn zero_bits(count: u32) -> Difficulty {
+        Difficulty(Requirement::ZeroBits(count))
+    }
+
+    pub fn hex_prefix(prefix: &str) -> Result<Difficulty, AocError> {
+        if !prefix.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) {
+            return Err(AocError::parse(prefix, "expected lowercase hexadecimal digits"));
+        }
+        Ok(Difficulty(Requirement::HexPrefix(prefix.to_string())))
+    }
+
+    /// A custom check, which the search cannot tell to be impossible up front.
+    pub fn predicate(found: impl Fn(&[u8]) -> bool + Send + Sync + 'static) -> Difficulty {
+        Difficulty(Requirement::Predicate(Box::new(found)))
+    }
+
+    /// Fails if no digest of `len` bytes can ever meet the difficulty, so a search would never end.
+    pub fn check(&self, len: usize) -> Result<(), AocError> {
+        let (needed, available, unit) = match &self.0 {
+            Requirement::ZeroNibbles(n) => (*n as usize, len.saturating_mul(2), "hexadecimal digits"),
+            Requirement::ZeroBits(n) => (*n as usize, len.saturating_mul(8), "bits"),
+            Requirement::HexPrefix(prefix) => (prefix.len(), len.saturating_mul(2), "hexadecimal digits"),
+            Requirement::Predicate(_) => return Ok(())
+        };
+        if needed > available {
+            return Err(AocError::NoSolution(format!("difficulty needs {} {} but the digest only has {}", needed, unit, available)));
+        }
+        Ok(())
+    }
+
+    pub fn is_met(&self, digest: &[u8]) -> bool {
+        match &self.0 {
+            Requirement::ZeroNibbles(n) => leading_zero_bits(digest) >= n.saturating_mul(4),
+            Requirement::ZeroBits(n) => leading_zero_bits(digest) >= *n,
+            Requirement::HexPrefix(prefix) => prefix.len() <= 2 * digest.len() && prefix.chars()
+                .enumerate()
+                .all(|(i, c)| c.to_digit(16) == Some(nibble(digest, i) as u32)),
+            Requirement::Predicate(found) => found(digest)
+        }
+    }
+}
+
+fn leading_zero_bits(digest: &[u8]) -> u32 {
+    let mut zeros = 0;
+    for byte in digest {
+        zeros += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    zeros
+}
+
+/// The `i`th hexadecimal digit.
+fn nibble(digest: &[u8], i: usize) -> u8 {
+    let byte = digest[i / 2];
+    if i.is_multiple_of(2) { byte >> 4 } else { byte & 0xf }
+}
+
+/// A hash that can be computed in two steps, so the state after hashing the
+/// key can be reused for every nonce.
+pub trait HashFunction: Sync {
+    type State: Clone + Sync;
+    type Output: AsRef<[u8]>;
+
+    /// Number of bytes in every digest.
+    fn digest_len(&self) -> usize;
+    fn start(&self, key: &[u8]) -> Self::State;
+    fn finish(&self, state: Self::State, data: &[u8]) -> Self::Output;
+}
+
+pub struct Md5;
+
+impl HashFunction for Md5 {
+    type State = md5::Context;
+    type Output = [u8; 16];
+
+    fn digest_len(&self) -> usize {
+        16
+    }
+
+    fn start(&self, key: &[u8]) -> md5::Context {
+        let mut context = md5::Context::new();
+        context.consume(key);
+        context
+    }
+
+    fn finish(&self, mut context: md5::Context, data: &[u8]) -> [u8; 16] {
+        context.consume(data);
+        context.compute().0
+    }
+}
+
+pub struct Sha1;
+
+impl HashFunction for Sha1 {
+    type State = sha1::Sha1;
+    type Output = [u8; 20];
+
+    fn digest_len(&self) -> usize {
+        20
+    }
+
+    fn start(&self, key: &[u8]) -> sha1::Sha1 {
+        sha1::Sha1::new_with_prefix(key)
+    }
+
+    fn finish(&self, mut state: sha1::Sha1, data: &[u8]) -> [u8; 20] {
+        state.update(data);
+        state.finalize().into()
+    }
+}
+
+pub struct Sha256;
+
+impl HashFunction for Sha256 {
+    type State = sha2::Sha256;
+    type Output = [u8; 32];
+
+    fn digest_len(&self) -> usize {
+        32
+    }
+
+    fn start(&self, key: &[u8]) -> sha2::Sha256 {
+        sha2::Sha256::new_with_prefix(key)
+    }
+
+    fn finish(&self, mut state: sha2::Sha256, data: &[u8]) -> [u8; 32] {
+        state.update(data);
+        state.finalize().into()
+    }
+}
+
+/// Hashes the key followed by a nonce without allocating: the key is hashed
+/// once up front and that state is cloned for every nonce.
+#[derive(Clone)]
+pub struct KeyHasher<H: HashFunction = Md5> {
+    hash_fn: H,
+    prefix: H::State,
+}
+
+impl KeyHasher {
+    pub fn new(key: &str) -> KeyHasher {
+        KeyHasher::with(Md5, key)
+    }
+}
+
+impl<H: HashFunction> KeyHasher<H> {
+    pub fn with(hash_fn: H, key: &str) -> KeyHasher<H> {
+        let prefix = hash_fn.start(key.as_bytes());
+        KeyHasher { hash_fn, prefix }
+    }
+
+    pub fn hash(&self, num: u64) -> H::Output {
+        let mut buf = [0u8; 20];
+        self.hash_fn.finish(self.prefix.clone(), decimal(num, &mut buf))
+    }
+}
+
+/// `num` in decimal, written to the end of `buf`, which fits any u64.
+fn decimal(mut num: u64, buf: &mut [u8; 20]) -> &[u8] {
+    let mut start = buf.len();
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (num % 10) as u8;
+        num /= 10;
+        if num == 0 {
+            return &buf[start..];
+        }
+    }
+}
+
+/// Straightforward hash of the key followed by a nonce, as a reference for `KeyHasher`.
+pub fn hash_formatted(key: &str, num: u64) -> Digest {
+    md5::compute(format!("{}{}", key, num))
+}
+
+pub fn advent_coin(key: &str, difficulty: &Difficulty) -> Result<u64, AocError> {
+    Ok(advent_coins(key, difficulty, 1)?[0].0)
+}
+
+/// The first `count` nonces that make an AdventCoin, with their digests.
+pub fn advent_coins(key: &str, difficulty: &Difficulty, count: usize) -> Result<Vec<(u64, [u8; 16])>, AocError> {
+    advent_coins_with(Md5, key, difficulty, count)
+}
+
+/// `advent_coins` with another hash than MD5.
+pub fn advent_coins_with<H: HashFunction>(hash_fn: H, key: &str, difficulty: &Difficulty, count: usize)
+                                          -> Result<Vec<(u64, H::Output)>, AocError> {
+    difficulty.check(hash_fn.digest_len())?;
+    let hasher = KeyHasher::with(hash_fn, key);
+    let mut coins = Vec::with_capacity(count);
+    let mut num = 0;
+    while coins.len() < count {
+        let digest = hasher.hash(num);
+        if difficulty.is_met(digest.as_ref()) {
+            coins.push((num, digest));
+        }
+        num += 1;
+    }
+    Ok(coins)
+}
+
+/// Lets the caller follow and stop a `Miner` running on other threads.
+#[derive(Debug, Default)]
+pub struct Progress {
+    tried: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl Progress {
+    pub fn new() -> Progress {
+        Progress::default()
+    }
+
+    /// Number of nonces tried so far, updated per chunk.
+    pub fn tried(&self) -> u64 {
+        self.tried.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Searches for AdventCoins on several threads at once.
+///
+/// Workers claim chunks of consecutive nonces in increasing order and stop
+/// claiming once a chunk starts beyond the best nonce found so far. Every
+/// chunk below it is searched to the end, so the smallest nonce wins no
+/// matter which worker finds what first.
+#[derive(Debug, Clone, Copy)]
+pub struct Miner {
+    workers: usize,
+    chunk: u64,
+}
+
+impl Miner {
+    pub fn new(workers: usize) -> Miner {
+        Miner { workers: workers.max(1), chunk: 10_000 }
+    }
+
+    /// Number of nonces a worker claims at a time.
+    pub fn with_chunk(self, chunk: u64) -> Miner {
+        Miner { chunk: chunk.max(1), ..self }
+    }
+
+    /// Smallest nonce that meets the `difficulty`, or `None` if cancelled first.
+    pub fn mine(&self, key: &str, difficulty: &Difficulty, progress: &Progress) -> Result<Option<u64>, AocError> {
+        self.mine_with(Md5, key, difficulty, progress)
+    }
+
+    /// `mine` with another hash than MD5.
+    pub fn mine_with<H: HashFunction>(&self, hash_fn: H, key: &str, difficulty: &Difficulty, progress: &Progress)
+                                      -> Result<Option<u64>, AocError> {
+        difficulty.check(hash_fn.digest_len())?;
+        let hasher = KeyHasher::with(hash_fn, key);
+        let next_chunk = AtomicU64::new(0);
+        let best = AtomicU64::new(u64::MAX);
+        thread::scope(|scope| {
+            for _ in 0..self.workers {
+                scope.spawn(|| {
+                    while !progress.is_cancelled() {
+                        let start = next_chunk.fetch_add(1, Ordering::Relaxed) * self.chunk;
+                        if start >= best.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        let mut tried = 0;
+                        for num in start..start + self.chunk {
+                            tried += 1;
+                            if difficulty.is_met(hasher.hash(num).as_ref()) {
+                                best.fetch_min(num, Ordering::Relaxed);
+                                break;
+                            }
+                        }
+                        progress.tried.fetch_add(tried, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+        Ok(match best.into_inner() {
+            u64::MAX => None,
+            num => Some(num)
+        })
+    }
+}
+
+impl Default for Miner {
+    /// One worker per available core.
+    fn default() -> Self {
+        Miner::new(thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, AocError> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(&self, key: &String) -> Result<String, AocError> {
+        let num = Miner::default().mine(key, &Difficulty::zero_nibbles(5), &Progress::new())?
+            .ok_or_else(|| AocError::NoSolution("mining was cancelled".to_string()))?;
+        Ok(num.to_string())
+    }
+
+    fn part2(&self, key: &String) -> Result<String, AocError> {
+        let num = Miner::default().mine(key, &Difficulty::zero_nibbles(6), &Progress::new())?
+            .ok_or_else(|| AocError::NoSolution("mining was cancelled".to_string()))?;
+        Ok(num.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advent_coin() {
+        assert_eq!(advent_coin("abcdef", &Difficulty::zero_nibbles(5)).unwrap(), 609_043);
+        assert_eq!(advent_coin("pqrstuv", &Difficulty::zero_nibbles(5)).unwrap(), 1_048_970);
+    }
+
+    /// Stub "hash" that is just the nonce, to test the search itself.
+    struct Nonce;
+
+    impl HashFunction for Nonce {
+        type State = ();
+        type Output = Vec<u8>;
+
+        /// Room for the longest nonce.
+        fn digest_len(&self) -> usize {
+            20
+        }
+
+        fn start(&self, _key: &[u8]) {}
+
+        fn finish(&self, _state: (), data: &[u8]) -> Vec<u8> {
+            data.to_vec()
+        }
+    }
+
+    #[test]
+    fn test_key_hasher() {
+        let hasher = KeyHasher::new("abcdef");
+        for num in [0, 9, 10, 609_043, 1_000_000, u64::MAX] {
+            assert_eq!(hasher.hash(num), hash_formatted("abcdef", num).0);
+        }
+    }
+
+    #[test]
+    fn test_difficulty() {
+        let digest = md5::compute("abcdef609043");
+        assert_eq!(format!("{:x}", digest), "000001dbbfa3a5c83a2d506429c7b00e");
+        assert!(Difficulty::zero_nibbles(5).is_met(&digest.0));
+        assert!(!Difficulty::zero_nibbles(6).is_met(&digest.0));
+        assert!(Difficulty::zero_bits(23).is_met(&digest.0));
+        assert!(!Difficulty::zero_bits(24).is_met(&digest.0));
+        assert!(Difficulty::hex_prefix("000001db").unwrap().is_met(&digest.0));
+        assert!(!Difficulty::hex_prefix("000001dc").unwrap().is_met(&digest.0));
+        assert!(Difficulty::hex_prefix("00000X").is_err());
+        assert!(Difficulty::predicate(|d| d[15] == 0x0e).is_met(&digest.0));
+    }
+
+    #[test]
+    fn test_advent_coins() {
+        let coins = advent_coins("abcdef", &Difficulty::hex_prefix("abc").unwrap(), 3).unwrap();
+        assert_eq!(coins.len(), 3);
+        assert!(coins.windows(2).all(|w| w[0].0 < w[1].0));
+        for (num, digest) in coins {
+            assert_eq!(md5::compute(format!("abcdef{}", num)).0, digest);
+            assert!(format!("{:x}", md5::Digest(digest)).starts_with("abc"));
+        }
+    }
+
+    #[test]
+    fn test_miner() {
+        for workers in 1..=4 {
+            let progress = Progress::new();
+            let miner = Miner::new(workers).with_chunk(7_919);
+            assert_eq!(miner.mine("abcdef", &Difficulty::zero_nibbles(5), &progress).unwrap(), Some(609_043));
+            assert!(progress.tried() > 609_043);
+        }
+    }
+
+    #[test]
+    fn test_miner_cancelled() {
+        let progress = Progress::new();
+        progress.cancel();
+        assert_eq!(Miner::new(2).mine("abcdef", &Difficulty::zero_nibbles(5), &progress).unwrap(), None);
+        assert_eq!(progress.tried(), 0);
+
+        // never finds anything, so only stops when cancelled halfway
+        let progress = Progress::new();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                while progress.tried() < 50_000 {
+                    thread::yield_now();
+                }
+                progress.cancel();
+            });
+            assert_eq!(Miner::new(2).with_chunk(1_000).mine("abcdef", &Difficulty::predicate(|_| false), &progress).unwrap(), None);
+        });
+        assert!(progress.tried() >= 50_000);
+    }
+
+    #[test]
+    fn test_hash_functions() {
+        let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!(hex(&KeyHasher::with(Sha1, "ab").hash(7)), "506ec6a4408cc9885fe362244ad97a7c78b4e820");
+        assert_eq!(hex(&KeyHasher::with(Sha256, "ab").hash(7)),
+                   "3b205fbd6113316438f3771b2a12b833eb306f1c04aee3c4a9596dffd81f5903");
+        let coins = advent_coins_with(Sha256, "abcdef", &Difficulty::zero_nibbles(3), 2).unwrap();
+        assert!(coins.iter().all(|(_, digest)| hex(digest).starts_with("000")));
+        assert_eq!(Miner::new(3).with_chunk(100).mine_with(Sha256, "abcdef", &Difficulty::zero_nibbles(3), &Progress::new()).unwrap(),
+                   Some(coins[0].0));
+    }
+
+    #[test]
+    fn test_stub_hash() {
+        let coins = advent_coins_with(Nonce, "", &Difficulty::hex_prefix("3132").unwrap(), 3).unwrap();
+        let nums: Vec<u64> = coins.iter().map(|(num, _)| *num).collect();
+        assert_eq!(nums, [12, 120, 121]);
+        let difficulty = Difficulty::predicate(|d| d == b"4242");
+        assert_eq!(Miner::new(4).with_chunk(10).mine_with(Nonce, "", &difficulty, &Progress::new()).unwrap(), Some(4242));
+    }
+
+    #[test]
+    fn test_impossible_difficulty() {
+        assert!(advent_coin("abcdef", &Difficulty::zero_nibbles(33)).is_err());
+        assert!(advent_coin("abcdef", &Difficulty::zero_bits(129)).is_err());
+        assert!(advent_coin("abcdef", &Difficulty::hex_prefix(&"0".repeat(33)).unwrap()).is_err());
+        assert!(Difficulty::hex_prefix("ABC").is_err());
+        assert!(Miner::new(2).mine("abcdef", &Difficulty::zero_nibbles(33), &Progress::new()).is_err());
+        assert!(Difficulty::zero_nibbles(40).check(20).is_ok());
+        assert!(Difficulty::zero_bits(161).check(20).is_err());
+        assert!(!Difficulty::zero_nibbles(u32::MAX).is_met(&[0; 16]));
+    }
+}
@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead};
+use std::ops::Not;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::AocError;
+use crate::solver::Solver;
+
+/// A single check a string can pass or fail.
+pub trait Rule {
+    fn name(&self) -> String;
+    fn check(&self, s: &str) -> bool;
+
+    /// Character offsets in a failing `s` that the rule objects to, if it
+    /// fails on something in particular rather than on something missing.
+    fn offending(&self, _s: &str) -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+/// Consecutive pairs of characters.
+fn digrams(s: &str) -> impl Iterator<Item = (char, char)> + '_ {
+    s.chars().zip(s.chars().skip(1))
+}
+
+/// At least this many of `aeiou`.
+pub struct MinVowels(pub usize);
+
+impl Rule for MinVowels {
+    fn name(&self) -> String {
+        format!("at least {} vowels", self.0)
+    }
+
+    fn check(&self, s: &str) -> bool {
+        s.chars().filter(|c| "aeiou".contains(*c)).count() >= self.0
+    }
+}
+
+/// The same letter twice in a row, like `xx`.
+pub struct DoubleLetter;
+
+impl Rule for DoubleLetter {
+    fn name(&self) -> String {
+        "a double letter".to_string()
+    }
+
+    fn check(&self, s: &str) -> bool {
+        digrams(s).any(|(a, b)| a == b)
+    }
+}
+
+/// None of these pairs of letters.
+pub struct ForbiddenDigrams(pub Vec<(char, char)>);
+
+impl Rule for ForbiddenDigrams {
+    fn name(&self) -> String {
+        let digrams: Vec<String> = self.0.iter().map(|(a, b)| format!("{}{}", a, b)).collect();
+        format!("none of {}", digrams.join(", "))
+    }
+
+    fn check(&self, s: &str) -> bool {
+        digrams(s).all(|d| !self.0.contains(&d))
+    }
+
+    /// Where the forbidden digrams start.
+    fn offending(&self, s: &str) -> Vec<usize> {
+        digrams(s).enumerate()
+            .filter(|(_, d)| self.0.contains(d))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// A pair of letters that appears twice without overlapping, like `xyxy` but not `aaa`.
+pub struct RepeatedPair;
+
+impl Rule for RepeatedPair {
+    fn name(&self) -> String {
+        "a repeated pair".to_string()
+    }
+
+    fn check(&self, s: &str) -> bool {
+        let mut first: HashMap<(char, char), usize> = HashMap::new();
+        digrams(s).enumerate().any(|(i, d)| i >= *first.entry(d).or_insert(i) + 2)
+    }
+
+    /// Where the pairs start that only repeat overlapping, like the second `aa` in `aaa`.
+    fn offending(&self, s: &str) -> Vec<usize> {
+        digrams(s).zip(digrams(s).skip(1))
+            .enumerate()
+            .filter(|(_, (d, next))| d == next)
+            .map(|(i, _)| i + 1)
+            .collect()
+    }
+}
+
+/// A letter that repeats with exactly one letter in between, like `xyx`.
+pub struct Sandwich;
+
+impl Rule for Sandwich {
+    fn name(&self) -> String {
+        "a letter sandwich".to_string()
+    }
+
+    fn check(&self, s: &str) -> bool {
+        s.chars().zip(s.chars().skip(2)).any(|(a, c)| a == c)
+    }
+}
+
+/// Rules combined with all/any/not.
+pub enum RuleSet {
+    Rule(Box<dyn Rule>),
+    All(Vec<RuleSet>),
+    Any(Vec<RuleSet>),
+    Not(Box<RuleSet>),
+}
+
+/// Result of every individual rule of a `RuleSet` for one string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub nice: bool,
+    pub rules: Vec<(String, bool)>,
+}
+
+/// A rule that made a string naughty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub rule: String,
+    pub offending: Vec<usize>,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rule)?;
+        if !self.offending.is_empty() {
+            let offsets: Vec<String> = self.offending.iter().map(|i| i.to_string()).collect();
+            write!(f, " at {}", offsets.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Report {
+    pub fn failed(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().filter(|(_, ok)| !ok).map(|(name, _)| name.as_str())
+    }
+}
+
+impl RuleSet {
+    pub fn rule(rule: impl Rule + 'static) -> RuleSet {
+        RuleSet::Rule(Box::new(rule))
+    }
+
+    /// Part 1: three vowels, a double letter and none of `ab`, `cd`, `pq` or `xy`.
+    pub fn nice() -> RuleSet {
+        RuleSet::All(vec![
+            RuleSet::rule(MinVowels(3)),
+            RuleSet::rule(DoubleLetter),
+            RuleSet::rule(ForbiddenDigrams(vec![('a', 'b'), ('c', 'd'), ('p', 'q'), ('x', 'y')])),
+        ])
+    }
+
+    /// Part 2: a repeated pair and a letter sandwich.
+    pub fn really_nice() -> RuleSet {
+        RuleSet::All(vec![RuleSet::rule(RepeatedPair), RuleSet::rule(Sandwich)])
+    }
+
+    /// Outcome of each individual rule, whether or not it decides the result.
+    pub fn report(&self, s: &str) -> Report {
+        let mut rules = Vec::new();
+        self.collect(s, &mut rules);
+        Report { nice: self.check(s), rules }
+    }
+
+    /// Why `s` is naughty: every individual rule that does not hold, with
+    /// the offending offsets. Empty for a nice string.
+    pub fn explain(&self, s: &str) -> Vec<Failure> {
+        let mut failures = Vec::new();
+        if !self.check(s) {
+            self.collect_failures(s, false, &mut failures);
+        }
+        failures
+    }
+
+    fn collect_failures(&self, s: &str, negated: bool, failures: &mut Vec<Failure>) {
+        match self {
+            RuleSet::Rule(rule) if negated && rule.check(s) =>
+                failures.push(Failure { rule: format!("not {}", rule.name()), offending: Vec::new() }),
+            RuleSet::Rule(rule) if !negated && !rule.check(s) =>
+                failures.push(Failure { rule: rule.name(), offending: rule.offending(s) }),
+            RuleSet::Rule(_) => {},
+            // only the children that fail too are to blame, in either polarity
+            RuleSet::All(sets) | RuleSet::Any(sets) => sets.iter()
+                .filter(|set| set.check(s) == negated)
+                .for_each(|set| set.collect_failures(s, negated, failures)),
+            RuleSet::Not(set) => set.collect_failures(s, !negated, failures)
+        }
+    }
+
+    fn collect(&self, s: &str, rules: &mut Vec<(String, bool)>) {
+        match self {
+            RuleSet::Rule(rule) => rules.push((rule.name(), rule.check(s))),
+            RuleSet::All(sets) | RuleSet::Any(sets) => sets.iter().for_each(|set| set.collect(s, rules)),
+            RuleSet::Not(set) => set.collect(s, rules)
+        }
+    }
+}
+
+impl Not for RuleSet {
+    type Output = RuleSet;
+
+    fn not(self) -> RuleSet {
+        RuleSet::Not(Box::new(self))
+    }
+}
+
+impl Rule for RuleSet {
+    fn name(&self) -> String {
+        let names = |sets: &[RuleSet]| sets.iter().map(|set| set.name()).collect::<Vec<_>>();
+        match self {
+            RuleSet::Rule(rule) => rule.name(),
+            RuleSet::All(sets) => format!("all of ({})", names(sets).join(", ")),
+            RuleSet::Any(sets) => format!("any of ({})", names(sets).join(", ")),
+            RuleSet::Not(set) => format!("not {}", set.name())
+        }
+    }
+
+    fn check(&self, s: &str) -> bool {
+        match self {
+            RuleSet::Rule(rule) => rule.check(s),
+            RuleSet::All(sets) => sets.iter().all(|set| set.check(s)),
+            RuleSet::Any(sets) => sets.iter().any(|set| set.check(s)),
+            RuleSet::Not(set) => !set.check(s)
+        }
+    }
+}
+
+// Byte-oriented versions of `RuleSet::nice` and `RuleSet::really_nice`, using
+// 26x26 tables indexed by pairs of lowercase letters instead of allocating.
+// Anything else, from capitals to multi-byte characters, is left to the rule
+// sets themselves so both always agree.
+
+const FORBIDDEN: [[bool; 26]; 26] = {
+    let mut table = [[false; 26]; 26];
+    table[0][1] = true; // ab
+    table[2][3] = true; // cd
+    table[15][16] = true; // pq
+    table[23][24] = true; // xy
+    table
+};
+
+fn letter(b: u8) -> usize {
+    (b - b'a') as usize
+}
+
+fn is_lowercase(s: &[u8]) -> bool {
+    s.iter().all(u8::is_ascii_lowercase)
+}
+
+pub fn is_nice_bytes(s: &[u8]) -> bool {
+    if !is_lowercase(s) {
+        return RuleSet::nice().check(&String::from_utf8_lossy(s));
+    }
+    let vowels = s.iter().filter(|b| matches!(b, b'a' | b'e' | b'i' | b'o' | b'u')).count();
+    let mut double = false;
+    for pair in s.windows(2) {
+        if FORBIDDEN[letter(pair[0])][letter(pair[1])] {
+            return false;
+        }
+        double |= pair[0] == pair[1];
+    }
+    vowels >= 3 && double
+}
+
+pub fn is_really_nice_bytes(s: &[u8]) -> bool {
+    if !is_lowercase(s) {
+        return RuleSet::really_nice().check(&String::from_utf8_lossy(s));
+    }
+    let sandwich = s.windows(3).any(|w| w[0] == w[2]);
+    if !sandwich {
+        return false;
+    }
+    let mut first = [[u32::MAX; 26]; 26];
+    for (i, pair) in s.windows(2).enumerate() {
+        let (a, b) = (letter(pair[0]), letter(pair[1]));
+        match first[a][b] {
+            u32::MAX => first[a][b] = i as u32,
+            j if i >= j as usize + 2 => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+pub fn is_nice(s: &str) -> bool {
+    is_nice_bytes(s.as_bytes())
+}
+
+pub fn is_really_nice(s: &str) -> bool {
+    is_really_nice_bytes(s.as_bytes())
+}
+
+/// Totals of classifying a stream with a string per line.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Classified {
+    pub lines: u64,
+    pub bytes: u64,
+    pub nice: u64,
+    pub really_nice: u64,
+}
+
+/// Classify every line of `reader` by both sets of rules, reusing one buffer.
+pub fn classify(reader: &mut dyn BufRead) -> io::Result<Classified> {
+    let mut totals = Classified::default();
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        totals.lines += 1;
+        totals.bytes += line.len() as u64;
+        let mut s = &line[..];
+        while let [rest @ .., b'\n' | b'\r'] = s {
+            s = rest;
+        }
+        totals.nice += is_nice_bytes(s) as u64;
+        totals.really_nice += is_really_nice_bytes(s) as u64;
+        line.clear();
+    }
+    Ok(totals)
+}
+
+/// `count` random strings of 16 lowercase letters, one per line, the same for
+/// the same `seed`.
+pub fn random_strings(count: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut strings = String::with_capacity(count * 17);
+    for _ in 0..count {
+        strings.extend((0..16).map(|_| rng.gen_range(b'a'..=b'z') as char));
+        strings.push('\n');
+    }
+    strings
+}
+
+pub fn count_nice(strings: &[String], is_nice: &dyn Fn(&str) -> bool) -> usize {
+    strings.iter()
+        .filter(|s| is_nice(s.as_str()))
+        .count()
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, AocError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, strings: &Vec<String>) -> Result<String, AocError> {
+        Ok(count_nice(strings, &is_nice).to_string())
+    }
+
+    fn part2(&self, strings: &Vec<String>) -> Result<String, AocError> {
+        Ok(count_nice(strings, &is_really_nice).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_nice() {
+        assert!(is_nice("ugknbfddgicrmopn"));
+        assert!(is_nice("aaa"));
+        assert!(!is_nice("jchzalrnumimnmhp"));
+        assert!(!is_nice("haegwjzuvuyypxyu"));
+        assert!(!is_nice("dvszwmarrgswjxmb"));
+    }
+
+    #[test]
+    fn test_is_really_nice() {
+        assert!(is_really_nice("qjhvhtzxzqqjkmpb"));
+        assert!(is_really_nice("xxyxx"));
+        assert!(!is_really_nice("uurcxstgmygtbstg"));
+        assert!(!is_really_nice("ieodomkazucvgmuy"));
+    }
+
+    #[test]
+    fn test_rules() {
+        assert!(MinVowels(3).check("xazegov"));
+        assert!(!MinVowels(3).check("dvszwmarrgswjxmb"));
+        assert!(DoubleLetter.check("abcdde"));
+        assert!(!ForbiddenDigrams(vec![('x', 'y')]).check("haegwjzuvuyypxyu"));
+        assert!(RepeatedPair.check("aabcdefgaa"));
+        assert!(!RepeatedPair.check("aaa"));
+        assert!(RepeatedPair.check("aaaa"));
+        assert!(RepeatedPair.check("ababccc"));
+        assert!(Sandwich.check("efe"));
+        assert!(!Sandwich.check("abba"));
+    }
+
+    #[test]
+    fn test_rule_set() {
+        let report = RuleSet::nice().report("haegwjzuvuyypxyu");
+        assert!(!report.nice);
+        assert_eq!(report.failed().collect::<Vec<_>>(), ["none of ab, cd, pq, xy"]);
+        assert_eq!(report.rules.len(), 3);
+        let vowels_or_sandwich = RuleSet::Any(vec![RuleSet::rule(MinVowels(3)), RuleSet::rule(Sandwich)]);
+        assert!(vowels_or_sandwich.check("xyx"));
+        assert!(!vowels_or_sandwich.check("xyz"));
+        let no_double = !RuleSet::rule(DoubleLetter);
+        assert!(no_double.check("abc"));
+        assert_eq!(no_double.name(), "not a double letter");
+        assert_eq!(no_double.report("abb").rules, [("a double letter".to_string(), true)]);
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(RuleSet::nice().explain("ugknbfddgicrmopn"), []);
+        let failures = RuleSet::nice().explain("haegwjzuvuyypxyu");
+        assert_eq!(failures, [Failure { rule: "none of ab, cd, pq, xy".to_string(), offending: vec![13] }]);
+        assert_eq!(failures[0].to_string(), "none of ab, cd, pq, xy at 13");
+        let failures = RuleSet::really_nice().explain("ieodomkazucvgmuy");
+        assert_eq!(failures, [Failure { rule: "a repeated pair".to_string(), offending: vec![] }]);
+        let failures = RuleSet::really_nice().explain("aaab");
+        assert_eq!(failures, [Failure { rule: "a repeated pair".to_string(), offending: vec![1] }]);
+        let failures = (!RuleSet::rule(DoubleLetter)).explain("abb");
+        assert_eq!(failures[0].to_string(), "not a double letter");
+        let nested = RuleSet::All(vec![
+            RuleSet::Any(vec![RuleSet::rule(MinVowels(3)), RuleSet::rule(Sandwich)]),
+            RuleSet::rule(DoubleLetter),
+        ]);
+        let failures = nested.explain("xyx");
+        assert_eq!(failures, [Failure { rule: "a double letter".to_string(), offending: vec![] }]);
+        let failures: Vec<String> = nested.explain("xyz").iter().map(|f| f.rule.clone()).collect();
+        assert_eq!(failures, ["at least 3 vowels", "a letter sandwich", "a double letter"]);
+        let failures = (!RuleSet::Any(vec![RuleSet::rule(DoubleLetter), RuleSet::rule(Sandwich)])).explain("xyx");
+        assert_eq!(failures, [Failure { rule: "not a letter sandwich".to_string(), offending: vec![] }]);
+        let failures = (!RuleSet::All(vec![RuleSet::rule(Sandwich), !RuleSet::rule(DoubleLetter)])).explain("xyx");
+        let failures: Vec<String> = failures.iter().map(|f| f.rule.clone()).collect();
+        assert_eq!(failures, ["not a letter sandwich", "a double letter"]);
+    }
+
+    #[test]
+    fn test_bytes_match_rules() {
+        let (nice, really_nice) = (RuleSet::nice(), RuleSet::really_nice());
+        let corpus = random_strings(2000, 5);
+        let mut strings: Vec<&str> = corpus.lines().collect();
+        strings.extend(["aaa", "aaaa", "xyxy", "ab", "", "a", "ababccc", "AAAeioxx"]);
+        strings.extend(["AAAA", "ABAB", "éxéxé", "aéaéa", "éé", "a1a1aa", "a b c dd", "aeiouXY", "xyXY"]);
+        for s in strings {
+            assert_eq!(is_nice(s), nice.check(s), "{}", s);
+            assert_eq!(is_really_nice(s), really_nice.check(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let input = b"ugknbfddgicrmopn\r\njchzalrnumimnmhp\nqjhvhtzxzqqjkmpb\nxxyxx";
+        let totals = classify(&mut &input[..]).unwrap();
+        assert_eq!(totals, Classified { lines: 4, bytes: input.len() as u64, nice: 1, really_nice: 2 });
+        let input = "AAAA\néxéxé\n".as_bytes();
+        let totals = classify(&mut &input[..]).unwrap();
+        assert_eq!(totals, Classified { lines: 2, bytes: input.len() as u64, nice: 0, really_nice: 2 });
+    }
+}
@@ -0,0 +1,594 @@
+use std::cmp::{max, min};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+use regex::Regex;
+use lazy_static::lazy_static;
+use crate::{parse_captures, AocError};
+use crate::image::{write_pbm, write_pgm, GreyGif, Rgb, BLACK};
+use crate::solver::Solver;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    On,
+    Off,
+    Toggle,
+    /// Set to the given brightness.
+    Set(u32),
+    /// Lower the brightness by the given amount.
+    Dim(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord {
+    pub x: u32,
+    pub y: u32,
+}
+
+pub struct Instruction {
+    action: Action,
+    from: Coord,
+    to: Coord,
+}
+
+impl Action {
+    pub fn new(s: &str) -> Result<Action, AocError> {
+        let amount = |n: &str| n.parse().map_err(|e| AocError::parse(s, e));
+        match s.split_once(' ') {
+            Some(("set", n)) => Ok(Action::Set(amount(n)?)),
+            Some(("dim", n)) => Ok(Action::Dim(amount(n)?)),
+            Some(_) => Err(AocError::parse(s, "unknown action")),
+            None => match s {
+                "on" => Ok(Action::On),
+                "off" => Ok(Action::Off),
+                "toggle" => Ok(Action::Toggle),
+                _ => Err(AocError::parse(s, "unknown action"))
+            }
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Action::new(s)
+    }
+}
+
+impl FromStr for Coord {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut xy = s.split(',').map(|v| v.parse::<u32>());
+        match (xy.next(), xy.next(), xy.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => Ok(Coord { x, y }),
+            _ => Err(AocError::parse(s, "invalid coordinate"))
+        }
+    }
+}
+
+from_captures!(Instruction { action, from, to });
+
+impl Instruction {
+    pub fn new(line: &str) -> Result<Instruction, AocError> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(
+                r"(?:turn )?(?P<action>[a-z]+(?: \d+)?) (?P<from>\d+,\d+) through (?P<to>\d+,\d+)").unwrap();
+        }
+        let instr: Instruction = parse_captures(&RE, line)?;
+        // any two opposite corners will do
+        let (from, to) = (instr.from, instr.to);
+        Ok(Instruction {
+            from: Coord { x: min(from.x, to.x), y: min(from.y, to.y) },
+            to: Coord { x: max(from.x, to.x), y: max(from.y, to.y) },
+            ..instr
+        })
+    }
+
+    /// Whether the rectangle lies within a grid of `width` by `height`.
+    pub fn check(&self, width: usize, height: usize) -> Result<(), OutOfRange> {
+        if self.to.x as usize >= width || self.to.y as usize >= height {
+            return Err(OutOfRange { from: self.from, to: self.to, width, height });
+        }
+        Ok(())
+    }
+}
+
+/// A rectangle that does not fit the grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutOfRange {
+    pub from: Coord,
+    pub to: Coord,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{} through {},{} is outside the {}x{} grid",
+               self.from.x, self.from.y, self.to.x, self.to.y, self.width, self.height)
+    }
+}
+
+impl Error for OutOfRange {}
+
+impl From<OutOfRange> for AocError {
+    fn from(e: OutOfRange) -> Self {
+        AocError::parse(&format!("{},{} through {},{}", e.from.x, e.from.y, e.to.x, e.to.y), e)
+    }
+}
+
+/// Puzzle grid size.
+pub const SIZE: usize = 1000;
+
+/// What the lights are and what the actions do to them.
+pub trait LightModel {
+    type Cell: Copy + Default;
+
+    fn act(&self, cell: Self::Cell, action: &Action) -> Self::Cell;
+    /// How much a light adds to the total.
+    fn value(&self, cell: Self::Cell) -> u64;
+}
+
+/// Part 1: lights are on or off.
+#[derive(Clone)]
+pub struct OnOff;
+
+impl LightModel for OnOff {
+    type Cell = bool;
+
+    fn act(&self, on: bool, action: &Action) -> bool {
+        match action {
+            Action::On => true,
+            Action::Off => false,
+            Action::Toggle => !on,
+            Action::Set(n) => *n > 0,
+            Action::Dim(n) => on && *n == 0
+        }
+    }
+
+    fn value(&self, on: bool) -> u64 {
+        on as u64
+    }
+}
+
+/// Part 2: lights have a brightness of zero or more.
+#[derive(Clone)]
+pub struct Brightness;
+
+impl LightModel for Brightness {
+    type Cell = u32;
+
+    fn act(&self, prev: u32, action: &Action) -> u32 {
+        match action {
+            Action::On => prev.saturating_add(1),
+            Action::Off => prev.saturating_sub(1),
+            Action::Toggle => prev.saturating_add(2),
+            Action::Set(n) => *n,
+            Action::Dim(n) => prev.saturating_sub(*n)
+        }
+    }
+
+    fn value(&self, brightness: u32) -> u64 {
+        brightness as u64
+    }
+}
+
+/// `Brightness` that never goes beyond a maximum.
+#[derive(Clone)]
+pub struct CappedBrightness(pub u32);
+
+impl LightModel for CappedBrightness {
+    type Cell = u32;
+
+    fn act(&self, prev: u32, action: &Action) -> u32 {
+        min(Brightness.act(prev, action), self.0)
+    }
+
+    fn value(&self, brightness: u32) -> u64 {
+        brightness as u64
+    }
+}
+
+/// Coloured lights: turning on adds the given colour, toggling inverts and
+/// `set`/`dim` work on all channels at once. Counts the sum of the channels.
+#[derive(Clone)]
+pub struct Colour(pub Rgb);
+
+impl LightModel for Colour {
+    type Cell = Rgb;
+
+    fn act(&self, prev: Rgb, action: &Action) -> Rgb {
+        let mut next = prev;
+        for (i, channel) in next.iter_mut().enumerate() {
+            *channel = match action {
+                Action::On => prev[i].saturating_add(self.0[i]),
+                Action::Off => BLACK[i],
+                Action::Toggle => 255 - prev[i],
+                Action::Set(n) => min(*n, 255) as u8,
+                Action::Dim(n) => prev[i].saturating_sub(min(*n, 255) as u8)
+            };
+        }
+        next
+    }
+
+    fn value(&self, colour: Rgb) -> u64 {
+        colour.iter().map(|c| *c as u64).sum()
+    }
+}
+
+/// Every light of a grid of any size.
+pub struct Grid<M: LightModel> {
+    model: M,
+    width: usize,
+    height: usize,
+    lights: Vec<M::Cell>,
+}
+
+impl<M: LightModel> Grid<M> {
+    pub fn new(model: M, width: usize, height: usize) -> Grid<M> {
+        Grid { model, width, height, lights: vec![M::Cell::default(); width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<M::Cell> {
+        if x < self.width && y < self.height { Some(self.lights[y * self.width + x]) } else { None }
+    }
+
+    pub fn apply(&mut self, instr: &Instruction) -> Result<(), OutOfRange> {
+        instr.check(self.width, self.height)?;
+        let (from, to) = (instr.from.x as usize, instr.to.x as usize);
+        for y in instr.from.y as usize..=instr.to.y as usize {
+            for light in &mut self.lights[y * self.width..][from..=to] {
+                *light = self.model.act(*light, &instr.action);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn total(&self) -> u64 {
+        self.lights.iter().map(|l| self.model.value(*l)).sum()
+    }
+
+    /// Highest value of any light.
+    pub fn peak(&self) -> u64 {
+        self.lights.iter().map(|l| self.model.value(*l)).max().unwrap_or(0)
+    }
+
+    /// Grey level of every light, row by row, scaled so that `peak` is white.
+    pub fn greyscale(&self, peak: u64) -> Vec<u8> {
+        let peak = peak.max(1);
+        self.lights.iter()
+            .map(|l| (self.model.value(*l).min(peak) * 255 / peak) as u8)
+            .collect()
+    }
+}
+
+pub fn count_lights<M: LightModel>(instructions: &[Instruction], model: M) -> Result<u64, OutOfRange> {
+    let mut grid = Grid::new(model, SIZE, SIZE);
+    for instr in instructions.iter() {
+        grid.apply(instr)?;
+    }
+    Ok(grid.total())
+}
+
+/// Call `frame` with the grid after every `every`th instruction and after the last one.
+pub fn frames<M: LightModel>(grid: &mut Grid<M>, instructions: &[Instruction], every: usize,
+                             frame: &mut dyn FnMut(&Grid<M>) -> Result<(), AocError>) -> Result<(), AocError> {
+    let every = every.max(1);
+    for (i, instr) in instructions.iter().enumerate() {
+        grid.apply(instr).map_err(|e| AocError::from(e).at_line(i + 1))?;
+        if (i + 1) % every == 0 || i + 1 == instructions.len() {
+            frame(grid)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameFormat {
+    /// A black and white PBM file per frame.
+    Pbm,
+    /// A greyscale PGM file per frame.
+    Pgm,
+    /// One animated GIF.
+    Gif,
+}
+
+impl FromStr for FrameFormat {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbm" => Ok(FrameFormat::Pbm),
+            "pgm" => Ok(FrameFormat::Pgm),
+            "gif" => Ok(FrameFormat::Gif),
+            _ => Err(AocError::parse(s, "unknown frame format"))
+        }
+    }
+}
+
+/// Write how the puzzle grid evolves, as `frameNNNN.pbm`/`.pgm` files in the
+/// `output` directory or as an animated GIF file. Greys are scaled to the
+/// brightest light of any frame. Returns the number of frames.
+pub fn animate<M: LightModel + Clone>(model: M, instructions: &[Instruction], every: usize,
+                                      format: FrameFormat, output: &str) -> Result<usize, AocError> {
+    let mut peak = 0;
+    frames(&mut Grid::new(model.clone(), SIZE, SIZE), instructions, every, &mut |grid| {
+        peak = peak.max(grid.peak());
+        Ok(())
+    })?;
+    let mut count = 0;
+    let mut grid = Grid::new(model, SIZE, SIZE);
+    if format == FrameFormat::Gif {
+        let file = File::create(output).map_err(|e| AocError::io(output, None, e))?;
+        let mut gif = GreyGif::new(BufWriter::new(file), SIZE, SIZE, 10)
+            .map_err(|e| AocError::io(output, None, e))?;
+        frames(&mut grid, instructions, every, &mut |grid| {
+            count += 1;
+            gif.add_frame(&grid.greyscale(peak)).map_err(|e| AocError::io(output, None, e))
+        })?;
+    } else {
+        fs::create_dir_all(output).map_err(|e| AocError::io(output, None, e))?;
+        frames(&mut grid, instructions, every, &mut |grid| {
+            count += 1;
+            let extension = if format == FrameFormat::Pbm { "pbm" } else { "pgm" };
+            let path = Path::new(output).join(format!("frame{:04}.{}", count, extension));
+            let path = path.to_string_lossy();
+            let mut w = BufWriter::new(File::create(path.as_ref()).map_err(|e| AocError::io(&path, None, e))?);
+            let grey = grid.greyscale(peak);
+            let written = match format {
+                FrameFormat::Pbm => write_pbm(&mut w, grid.width(), grid.height(), &grey),
+                _ => write_pgm(&mut w, grid.width(), grid.height(), &grey)
+            };
+            written.and_then(|_| w.flush()).map_err(|e| AocError::io(&path, None, e))
+        })?;
+    }
+    Ok(count)
+}
+
+/// Lights grouped into the rectangles that no instruction's edge cuts through,
+/// so that an instruction updates one value per rectangle rather than one per
+/// light. Its size depends on the number of instructions, not on their
+/// coordinates.
+struct CompressedGrid<M: LightModel> {
+    model: M,
+    /// Start of each column of rectangles, the last one being the end of the previous.
+    xs: Vec<u64>,
+    ys: Vec<u64>,
+    cells: Vec<M::Cell>,
+}
+
+impl<M: LightModel> CompressedGrid<M> {
+    fn new(model: M, instructions: &[Instruction]) -> CompressedGrid<M> {
+        let edges = |coord: &dyn Fn(&Coord) -> u32| {
+            let mut edges: Vec<u64> = instructions.iter()
+                .flat_map(|i| [coord(&i.from) as u64, coord(&i.to) as u64 + 1])
+                .collect();
+            edges.sort_unstable();
+            edges.dedup();
+            edges
+        };
+        let xs = edges(&|c| c.x);
+        let ys = edges(&|c| c.y);
+        let size = xs.len().saturating_sub(1) * ys.len().saturating_sub(1);
+        CompressedGrid { model, xs, ys, cells: vec![M::Cell::default(); size] }
+    }
+
+    /// Indices of the rectangles between two edges.
+    fn span(edges: &[u64], from: u32, to: u32) -> std::ops::Range<usize> {
+        let index = |edge| edges.binary_search(&edge).expect("edge of an instruction");
+        index(from as u64)..index(to as u64 + 1)
+    }
+
+    fn apply(&mut self, instruction: &Instruction) {
+        let width = self.xs.len() - 1;
+        let columns = CompressedGrid::<M>::span(&self.xs, instruction.from.x, instruction.to.x);
+        for row in CompressedGrid::<M>::span(&self.ys, instruction.from.y, instruction.to.y) {
+            for cell in &mut self.cells[row * width..][columns.clone()] {
+                *cell = self.model.act(*cell, &instruction.action);
+            }
+        }
+    }
+
+    /// Sum of all lights, each rectangle weighted by its area. Even the number of lights
+    /// can reach 2^64, so this is a u128.
+    fn total(&self) -> u128 {
+        let width = self.xs.len().saturating_sub(1);
+        self.cells.iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let (row, column) = (i / width, i % width);
+                let area = (self.xs[column + 1] - self.xs[column]) as u128 * (self.ys[row + 1] - self.ys[row]) as u128;
+                self.model.value(*cell) as u128 * area
+            })
+            .sum()
+    }
+}
+
+/// Same as `count_lights`, but for any grid size using coordinate compression.
+pub fn count_lights_compressed<M: LightModel>(instructions: &[Instruction], model: M) -> u128 {
+    let mut grid = CompressedGrid::new(model, instructions);
+    for instr in instructions {
+        grid.apply(instr);
+    }
+    grid.total()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, AocError> {
+    input.lines()
+        .enumerate()
+        .map(|(i, s)| Instruction::new(s).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>, AocError> {
+        let instructions = parse(input)?;
+        for (i, instr) in instructions.iter().enumerate() {
+            instr.check(SIZE, SIZE).map_err(|e| AocError::from(e).at_line(i + 1))?;
+        }
+        Ok(instructions)
+    }
+
+    fn part1(&self, instructions: &Vec<Instruction>) -> Result<String, AocError> {
+        Ok(count_lights_compressed(instructions, OnOff).to_string())
+    }
+
+    fn part2(&self, instructions: &Vec<Instruction>) -> Result<String, AocError> {
+        Ok(count_lights_compressed(instructions, Brightness).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::WHITE;
+
+    #[test]
+    fn test_on_off() {
+        let instructions = parse("turn on 0,0 through 999,999").unwrap();
+        assert_eq!(count_lights(&instructions, OnOff).unwrap(), 1_000_000);
+        let instructions = parse("toggle 0,0 through 999,0").unwrap();
+        assert_eq!(count_lights(&instructions, OnOff).unwrap(), 1000);
+        let instructions = parse("turn on 0,0 through 999,999\nturn off 499,499 through 500,500").unwrap();
+        assert_eq!(count_lights(&instructions, OnOff).unwrap(), 999_996);
+    }
+
+    #[test]
+    fn test_brightness() {
+        let instructions = parse("turn on 0,0 through 0,0").unwrap();
+        assert_eq!(count_lights(&instructions, Brightness).unwrap(), 1);
+        let instructions = parse("toggle 0,0 through 999,999").unwrap();
+        assert_eq!(count_lights(&instructions, Brightness).unwrap(), 2_000_000);
+        let instructions = parse("turn off 0,0 through 0,0").unwrap();
+        assert_eq!(count_lights(&instructions, Brightness).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_models() {
+        let instructions = parse("set 5 0,0 through 9,9\ndim 3 0,0 through 4,9\ntoggle 0,0 through 0,0").unwrap();
+        assert_eq!(count_lights(&instructions, Brightness).unwrap(), 50 * 5 + 50 * 2 + 2);
+        assert_eq!(count_lights(&instructions, CappedBrightness(4)).unwrap(), 50 * 4 + 50 + 2);
+        assert_eq!(count_lights(&instructions, OnOff).unwrap(), 51);
+        assert_eq!(count_lights_compressed(&instructions, CappedBrightness(4)), 50 * 4 + 50 + 2);
+
+        let mut grid = Grid::new(Colour([200, 100, 0]), 2, 1);
+        for line in ["turn on 0,0 through 1,0", "turn on 0,0 through 0,0", "toggle 1,0 through 1,0"] {
+            grid.apply(&Instruction::new(line).unwrap()).unwrap();
+        }
+        assert_eq!(grid.get(0, 0), Some([255, 200, 0]));
+        assert_eq!(grid.get(1, 0), Some([55, 155, 255]));
+        assert_eq!(grid.total(), 455 + 465);
+    }
+
+    #[test]
+    fn test_frames() {
+        let instructions = parse("turn on 0,0 through 1,0\ntoggle 1,0 through 2,0\nturn off 0,0 through 0,0").unwrap();
+        let mut greys = Vec::new();
+        frames(&mut Grid::new(Brightness, 4, 1), &instructions, 2, &mut |grid| {
+            greys.push(grid.greyscale(3));
+            Ok(())
+        }).unwrap();
+        assert_eq!(greys, [vec![85, 255, 170, 0], vec![0, 255, 170, 0]]);
+        let mut count = 0;
+        let result = frames(&mut Grid::new(OnOff, 2, 1), &instructions, 1, &mut |_| {
+            count += 1;
+            Ok(())
+        });
+        // the second instruction does not fit
+        assert!(result.is_err());
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_animate() {
+        let dir = std::env::temp_dir().join(format!("aoc-day6-{}", std::process::id()));
+        let output = dir.to_string_lossy();
+        let instructions = parse("turn on 0,0 through 9,9\ntoggle 0,0 through 999,999\nturn off 0,0 through 0,0").unwrap();
+        assert_eq!(animate(Brightness, &instructions, 2, FrameFormat::Pgm, &output).unwrap(), 2);
+        let frame = fs::read(dir.join("frame0002.pgm")).unwrap();
+        assert_eq!(&frame[..17], b"P5\n1000 1000\n255\n");
+        assert_eq!(frame.len(), 17 + 1_000_000);
+        assert_eq!(animate(OnOff, &instructions, 1, FrameFormat::Pbm, &output).unwrap(), 3);
+        assert_eq!(fs::read(dir.join("frame0003.pbm")).unwrap().len(), 13 + 125_000);
+        let gif = dir.join("lights.gif");
+        assert_eq!(animate(Brightness, &instructions, 1, FrameFormat::Gif, &gif.to_string_lossy()).unwrap(), 3);
+        assert_eq!(&fs::read(&gif).unwrap()[..6], b"GIF89a");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compressed() {
+        let data = "turn on 0,0 through 999,999\ntoggle 0,0 through 999,0\nturn off 499,499 through 500,500\n\
+                    toggle 250,100 through 750,900\nturn on 400,0 through 420,999";
+        let instructions = parse(data).unwrap();
+        assert_eq!(count_lights_compressed(&instructions, OnOff), count_lights(&instructions, OnOff).unwrap().into());
+        assert_eq!(count_lights_compressed(&instructions, Brightness), count_lights(&instructions, Brightness).unwrap().into());
+        assert_eq!(count_lights_compressed(&[], OnOff), 0);
+    }
+
+    #[test]
+    fn test_compressed_large() {
+        let instructions = parse("turn on 0,0 through 99999,99999\ntoggle 0,0 through 4294967295,0").unwrap();
+        assert_eq!(count_lights_compressed(&instructions, OnOff), 100_000 * 99_999 + 4_294_967_296 - 100_000);
+        assert_eq!(count_lights_compressed(&instructions, Brightness), 100_000 * 100_000 + 2 * 4_294_967_296);
+    }
+
+    #[test]
+    fn test_compressed_full_range() {
+        let instructions = parse("turn on 0,0 through 4294967295,4294967295").unwrap();
+        assert_eq!(count_lights_compressed(&instructions, OnOff), 1 << 64);
+        assert_eq!(count_lights_compressed(&instructions, Colour(WHITE)), 765 << 64);
+        let instructions = parse("turn on 0,0 through 4294967295,4294967295\n\
+                                  toggle 0,0 through 4294967295,4294967295\n\
+                                  turn off 0,0 through 0,0").unwrap();
+        assert_eq!(count_lights_compressed(&instructions, Brightness), 3 * (1 << 64) - 1);
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid::new(Brightness, 3, 2);
+        grid.apply(&Instruction::new("turn on 2,1 through 1,0").unwrap()).unwrap();
+        assert_eq!(grid.total(), 4);
+        assert_eq!(grid.get(0, 0), Some(0));
+        assert_eq!(grid.get(1, 1), Some(1));
+        assert_eq!(grid.get(3, 1), None);
+        let instr = Instruction::new("toggle 0,0 through 3,1").unwrap();
+        assert_eq!(grid.apply(&instr),
+                   Err(OutOfRange { from: Coord { x: 0, y: 0 }, to: Coord { x: 3, y: 1 }, width: 3, height: 2 }));
+        assert_eq!(grid.total(), 4);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let instructions = parse("turn on 0,0 through 1200,5").unwrap();
+        let e = count_lights(&instructions, OnOff).unwrap_err();
+        assert_eq!(e.to_string(), "0,0 through 1200,5 is outside the 1000x1000 grid");
+        assert_eq!(count_lights_compressed(&instructions, OnOff), 1201 * 6);
+        assert!(Day6.parse("toggle 0,0 through 1,1\nturn on 0,0 through 1200,5").is_err());
+    }
+
+    #[test]
+    fn test_invalid_instruction() {
+        assert!(Instruction::new("turn sideways 0,0 through 1,1").is_err());
+        assert!(Instruction::new("toggle 0,0 to 1,1").is_err());
+        assert!(Instruction::new("toggle 3 0,0 through 1,1").is_err());
+        assert!(Instruction::new("set 0,0 through 1,1").is_err());
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use crate::AocError;
+use crate::solver::Solver;
+
+pub type Wires<'a> = HashMap<&'a str, u16>;
+
+#[derive(Debug)]
+pub enum Gate {
+    Assign,
+    And,
+    Or,
+    Not,
+    LShift,
+    RShift,
+}
+
+impl Gate {
+    pub fn new(s: &str) -> Result<Gate, AocError> {
+        match s {
+            "" => Ok(Gate::Assign),
+            "AND" => Ok(Gate::And),
+            "OR" => Ok(Gate::Or),
+            "NOT" => Ok(Gate::Not),
+            "LSHIFT" => Ok(Gate::LShift),
+            "RSHIFT" => Ok(Gate::RShift),
+            _ => Err(AocError::parse(s, "unknown gate"))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct WireValue<'a> {
+    wire: Option<&'a str>,
+    value: Option<u16>,
+}
+
+impl WireValue<'_> {
+    pub fn new(s: &str) -> WireValue<'_> {
+        match s.parse() {
+            Ok(i) => WireValue::new_value(i),
+            Err(_) => WireValue::new_wire(s)
+        }
+    }
+
+    pub fn new_value<'a>(v: u16) -> WireValue<'a> {
+        WireValue { wire: None, value: Some(v) }
+    }
+
+    pub fn new_wire(w: &str) -> WireValue<'_> {
+        WireValue { wire: Some(w), value: None }
+    }
+
+    pub fn apply<'a>(wv: WireValue<'a>, wires: &Wires) -> WireValue<'a> {
+        WireValue {
+            wire: wv.wire,
+            value: match wv.value {
+                Some(v) => Some(v),
+                None => match wv.wire {
+                    Some(k) => if wires.contains_key(k) {
+                        Some(wires[k])
+                    } else {
+                        None
+                    },
+                    None => unreachable!("no value AND no wire name")
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction<'a> {
+    gate: Gate,
+    lh1: WireValue<'a>,
+    lh2: Option<WireValue<'a>>,
+    rh: &'a str,
+}
+
+fn expect_token(line: &str, token: &str, expected: &str) -> Result<(), AocError> {
+    if token == expected {
+        Ok(())
+    } else {
+        Err(AocError::parse(line, format!("expected '{}', got '{}'", expected, token)))
+    }
+}
+
+impl Instruction<'_> {
+    pub fn new(line: &str) -> Result<Instruction<'_>, AocError> {
+        let tokens: Vec<&str> = line.split(' ').collect();
+        match tokens.len() {
+            3 => {
+                expect_token(line, tokens[1], "->")?;
+                Ok(Instruction {
+                    gate: Gate::Assign,
+                    lh1: WireValue::new(tokens[0]),
+                    lh2: None,
+                    rh: tokens[2],
+                })
+            },
+            4 => {
+                expect_token(line, tokens[0], "NOT")?;
+                expect_token(line, tokens[2], "->")?;
+                Ok(Instruction {
+                    gate: Gate::Not,
+                    lh1: WireValue::new(tokens[1]),
+                    lh2: None,
+                    rh: tokens[3],
+                })
+            },
+            5 => {
+                expect_token(line, tokens[3], "->")?;
+                let gate = Gate::new(tokens[1])?;
+                let lh2 = WireValue::new(tokens[2]);
+                if let (Gate::LShift | Gate::RShift, Some(16..)) = (&gate, lh2.value) {
+                    return Err(AocError::parse(line, "shift amount must be below 16"));
+                }
+                Ok(Instruction {
+                    gate,
+                    lh1: WireValue::new(tokens[0]),
+                    lh2: Some(lh2),
+                    rh: tokens[4],
+                })
+            },
+            _ => Err(AocError::parse(line, format!("unexpected number of tokens: {}", tokens.len())))
+        }
+    }
+
+    pub fn new_assign(value: u16, wire: &str) -> Instruction<'_> {
+        Instruction {
+            gate: Gate::Assign,
+            lh1: WireValue::new_value(value),
+            lh2: None,
+            rh: wire
+        }
+    }
+
+    pub fn apply<'a>(instr: Instruction<'a>, wires: &Wires) -> Instruction<'a> {
+        Instruction {
+            gate: instr.gate,
+            lh1: WireValue::apply(instr.lh1, wires),
+            lh2: instr.lh2.map(|wv| WireValue::apply(wv, wires)),
+            rh: instr.rh
+        }
+    }
+
+    pub fn all_signals_present(&self) -> bool {
+        match self.lh1.value {
+            Some(_) => match &self.lh2 {
+                Some(wv2) => wv2.value.is_some(),
+                None => true
+            },
+            None => false
+        }
+    }
+}
+
+fn eval<'a>(instructions: Vec<Instruction<'a>>, wires: &Wires) -> Vec<Instruction<'a>> {
+    instructions
+        .into_iter()
+        .map(|instr| {
+            if instr.all_signals_present() {
+                match instr.gate {
+                    Gate::And => Instruction::new_assign(instr.lh1.value.unwrap() & instr.lh2.as_ref().unwrap().value.unwrap(), instr.rh),
+                    Gate::Or => Instruction::new_assign(instr.lh1.value.unwrap() | instr.lh2.as_ref().unwrap().value.unwrap(), instr.rh),
+                    Gate::Not => Instruction::new_assign(!instr.lh1.value.unwrap(), instr.rh),
+                    // a wire can still carry a shift of 16 or more, which clears every bit
+                    Gate::LShift => Instruction::new_assign(instr.lh1.value.unwrap().checked_shl(instr.lh2.as_ref().unwrap().value.unwrap().into()).unwrap_or(0), instr.rh),
+                    Gate::RShift => Instruction::new_assign(instr.lh1.value.unwrap().checked_shr(instr.lh2.as_ref().unwrap().value.unwrap().into()).unwrap_or(0), instr.rh),
+                    _ => instr
+                }
+            } else {
+                Instruction::apply(instr, wires)
+            }
+        })
+        .collect()
+}
+
+pub fn run<'a>(instructions: Vec<Instruction<'a>>, wires: &mut Wires<'a>) {
+    run_until_stuck(instructions, wires, 0)
+}
+
+/// Gates whose inputs are never driven would keep the circuit unresolved forever, so give up
+/// once two rounds in a row (one to pick up signals, one to evaluate) change nothing.
+fn run_until_stuck<'a>(instructions: Vec<Instruction<'a>>, wires: &mut Wires<'a>, idle: usize) {
+// break out?
+    if instructions.is_empty() || idle == 2 {
+        return;
+    }
+    let before = (instructions.len(), wires.len());
+// resolve all fully signalled gates to assignments
+    let mut instructions: Vec<Instruction<'_>> = eval(instructions, wires);
+// move all assignments to the wires hashtable
+    instructions.retain(|instr| {
+        match instr.gate {
+            Gate::Assign => {
+                match instr.lh1.value {
+                    Some(v) => {
+                        if !wires.contains_key(instr.rh) {
+                            wires.insert(instr.rh, v);
+                        }
+                        false // drop
+                    },
+                    None => true // retain
+                }
+            }
+            _ => true // retain
+        }
+    });
+    let idle = if (instructions.len(), wires.len()) == before { idle + 1 } else { 0 };
+    run_until_stuck(instructions, wires, idle);
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction<'_>>, AocError> {
+    input.lines()
+        .enumerate()
+        .map(|(i, s)| Instruction::new(s).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+pub fn signal_a(data: &str, b: Option<u16>) -> Result<u16, AocError> {
+    let instructions = parse(data)?;
+    let mut wires: Wires = HashMap::new();
+    if let Some(b_value) = b {
+        wires.insert("b", b_value);
+    }
+    run(instructions, &mut wires);
+    wires.get("a").copied().ok_or_else(|| AocError::NoSolution("no signal on wire a".to_string()))
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, AocError> {
+        // instructions borrow from their line, so only validate here
+        parse(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, data: &String) -> Result<String, AocError> {
+        Ok(signal_a(data, None)?.to_string())
+    }
+
+    fn part2(&self, data: &String) -> Result<String, AocError> {
+        let a_value = signal_a(data, None)?;
+        Ok(signal_a(data, Some(a_value))?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit() {
+        let data = "123 -> x\n\
+                    456 -> y\n\
+                    x AND y -> d\n\
+                    x OR y -> e\n\
+                    x LSHIFT 2 -> f\n\
+                    y RSHIFT 2 -> g\n\
+                    NOT x -> h\n\
+                    NOT y -> i\n";
+        let mut wires: Wires = HashMap::new();
+        run(parse(data).unwrap(), &mut wires);
+        assert_eq!(wires["d"], 72);
+        assert_eq!(wires["e"], 507);
+        assert_eq!(wires["f"], 492);
+        assert_eq!(wires["g"], 114);
+        assert_eq!(wires["h"], 65412);
+        assert_eq!(wires["i"], 65079);
+        assert_eq!(wires["x"], 123);
+        assert_eq!(wires["y"], 456);
+    }
+
+    #[test]
+    fn test_invalid_instruction() {
+        assert!(Instruction::new("x XOR y -> z").is_err());
+        assert!(Instruction::new("x AND y => z").is_err());
+        assert!(Instruction::new("x").is_err());
+        assert!(Instruction::new("x LSHIFT 16 -> a").is_err());
+        assert!(Instruction::new("x RSHIFT 15 -> a").is_ok());
+    }
+
+    #[test]
+    fn test_no_signal() {
+        assert!(signal_a("1 -> b\n", None).is_err());
+        assert!(signal_a("x AND y -> a\n", None).is_err());
+        let mut wires: Wires = HashMap::new();
+        run(parse("1 -> b\n16 -> s\nb LSHIFT s -> a\n").unwrap(), &mut wires);
+        assert_eq!(wires["a"], 0);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::AocError;
+use crate::solver::Solver;
+
+pub fn count_decoded(s: &str) -> (usize, usize) {
+    let mut sz = 0;
+    let mut backslash = false;
+    let mut skip = 0;
+    // skip enclosing double quotes
+    for c in s[1..s.len() - 1].chars() {
+        if skip > 0 {
+            skip -= 1
+        } else if backslash {
+            if c == 'x' {
+                skip = 2
+            }
+            sz += 1; // count backslash'ed character
+            backslash = false;
+        } else if c == '\\' {
+            backslash = true;
+        } else {
+            sz += 1
+        }
+    }
+    (s.len(), sz)
+}
+
+pub fn count_encoded(s: &str) -> (usize, usize) {
+    let mut sz = 0;
+    for c in s.chars() {
+        match c {
+            '\\' | '"' => sz += 2,
+            _ => sz += 1
+        }
+    }
+    (s.len(), 2 + sz) // extra 2 for quotes
+}
+
+pub fn count_all(lines: &[String], count: &dyn Fn(&str) -> (usize, usize)) -> (usize, usize) {
+    lines.iter()
+        .map(|s| count(s.as_str()))
+        .fold((0, 0), |acc, sz| (acc.0 + sz.0, acc.1 + sz.1))
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, AocError> {
+        input.lines()
+            .enumerate()
+            .map(|(i, s)| if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+                Ok(s.to_string())
+            } else {
+                Err(AocError::parse(s, "expected a double-quoted string").at_line(i + 1))
+            })
+            .collect()
+    }
+
+    fn part1(&self, data: &Vec<String>) -> Result<String, AocError> {
+        let counts = count_all(data, &count_decoded);
+        Ok((counts.0 - counts.1).to_string())
+    }
+
+    fn part2(&self, data: &Vec<String>) -> Result<String, AocError> {
+        let counts = count_all(data, &count_encoded);
+        Ok((counts.1 - counts.0).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_decoded() {
+        assert_eq!(count_decoded(r#""""#), (2, 0));
+        assert_eq!(count_decoded(r#""abc""#), (5, 3));
+        assert_eq!(count_decoded(r#""aaa\"aaa""#), (10, 7));
+        assert_eq!(count_decoded(r#""\x27""#), (6, 1));
+    }
+
+    #[test]
+    fn test_count_encoded() {
+        assert_eq!(count_encoded(r#""""#), (2, 6));
+        assert_eq!(count_encoded(r#""abc""#), (5, 9));
+        assert_eq!(count_encoded(r#""aaa\"aaa""#), (10, 16));
+        assert_eq!(count_encoded(r#""\x27""#), (6, 11));
+    }
+
+    #[test]
+    fn test_unquoted() {
+        assert!(Day8.parse("\"a\"\n\n").is_err());
+        assert!(Day8.parse("\"").is_err());
+        assert!(Day8.parse("abc").is_err());
+        assert_eq!(Day8.parse("\"\"\n\"a\"").unwrap().len(), 2);
+    }
+}
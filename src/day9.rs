@@ -0,0 +1,124 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use std::collections::{HashMap, HashSet};
+use crate::{parse_captures, AocError};
+use crate::solver::Solver;
+
+pub type Locations = HashSet<String>;
+pub type Distances = HashMap<String, HashMap<String, u32>>;
+pub type Cmp = dyn Fn(u32, u32) -> bool;
+
+struct Route {
+    from: String,
+    to: String,
+    dist: u32,
+}
+
+from_captures!(Route { from, to, dist });
+
+pub fn parse(input: &str) -> Result<(Locations, Distances), AocError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(?P<from>[a-zA-Z]+) to (?P<to>[a-zA-Z]+) = (?P<dist>\d+)").unwrap();
+    }
+    let mut stars = HashSet::new();
+    let mut dist = HashMap::new();
+    for (i, line) in input.lines().enumerate() {
+        let route: Route = parse_captures(&RE, line).map_err(|e| e.at_line(i + 1))?;
+        stars.insert(route.from.clone());
+        stars.insert(route.to.clone());
+        dist.entry(route.from.clone()).or_insert_with(HashMap::new).insert(route.to.clone(), route.dist);
+        dist.entry(route.to).or_insert_with(HashMap::new).insert(route.from, route.dist);
+    }
+    Ok((stars, dist))
+}
+
+fn tsp_starting_from(start: &str, locations: &Locations, distances: &Distances,
+                     cmp: &Cmp, init: u32) -> Option<u32> {
+    let mut total_dist = 0;
+    let mut path: Vec<&str> = Vec::new();
+    let mut current: &str = start;
+    loop {
+        path.push(current);
+        if path.len() == locations.len() {
+            break;
+        }
+        let mut dist: u32 = init;
+        let mut next: Option<&String> = None;
+        for (name, d) in &distances[current] {
+            if !path.contains(&name.as_str()) && cmp(*d, dist) {
+                dist = *d;
+                next = Some(name);
+            }
+        }
+        match next {
+            Some(name) => {
+                current = name;
+                total_dist += dist;
+            },
+            None => return None
+        };
+    }
+    Some(total_dist)
+}
+
+pub fn tsp(locations: &Locations, distances: &Distances, cmp: &Cmp, init: u32) -> Result<u32, AocError> {
+    let mut distance = None;
+    for loc in locations {
+        if let Some(d) = tsp_starting_from(loc.as_str(), locations, distances, cmp, init) {
+            if distance.is_none_or(|best| cmp(d, best)) {
+                distance = Some(d);
+            }
+        }
+    }
+    distance.ok_or_else(|| AocError::NoSolution("no route visits every location".to_string()))
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = (Locations, Distances);
+
+    fn parse(&self, input: &str) -> Result<(Locations, Distances), AocError> {
+        parse(input)
+    }
+
+    fn part1(&self, (locations, distances): &(Locations, Distances)) -> Result<String, AocError> {
+        Ok(tsp(locations, distances, &|a, b| a < b, u32::MAX)?.to_string())
+    }
+
+    fn part2(&self, (locations, distances): &(Locations, Distances)) -> Result<String, AocError> {
+        Ok(tsp(locations, distances, &|a, b| a > b, 0)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> (Locations, Distances) {
+        parse("London to Dublin = 464\n\
+               London to Belfast = 518\n\
+               Dublin to Belfast = 141\n").unwrap()
+    }
+
+    #[test]
+    fn test_shortest() {
+        let (locations, distances) = example();
+        assert_eq!(tsp(&locations, &distances, &|a, b| a < b, u32::MAX).unwrap(), 605);
+    }
+
+    #[test]
+    fn test_longest() {
+        let (locations, distances) = example();
+        assert_eq!(tsp(&locations, &distances, &|a, b| a > b, 0).unwrap(), 982);
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let (locations, distances) = parse("London to Dublin = 464\n\
+                                            Paris to Belfast = 141\n").unwrap();
+        assert!(tsp(&locations, &distances, &|a, b| a < b, u32::MAX).is_err());
+        let (locations, distances) = parse("").unwrap();
+        assert!(tsp(&locations, &distances, &|a, b| a < b, u32::MAX).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+#[derive(Debug)]
+pub enum AocError {
+    /// Reading an input file failed, optionally at a specific (1-based) line.
+    Io {
+        path: String,
+        line: Option<usize>,
+        cause: io::Error,
+    },
+    /// A line of input could not be parsed.
+    Parse {
+        line: Option<usize>,
+        input: String,
+        cause: String,
+    },
+    /// A computed value does not fit its type.
+    Overflow(String),
+    /// Well-formed input that the puzzle has no answer for.
+    NoSolution(String),
+    /// Only parts 1 and 2 exist.
+    InvalidPart(u32),
+}
+
+impl AocError {
+    pub fn io(path: &str, line: Option<usize>, cause: io::Error) -> Self {
+        AocError::Io { path: path.to_string(), line, cause }
+    }
+
+    /// An empty `input` is left out of the message, for causes that already describe it.
+    pub fn parse(input: &str, cause: impl Display) -> Self {
+        AocError::Parse { line: None, input: input.to_string(), cause: cause.to_string() }
+    }
+
+    /// TOML that did not parse, reported at the offending line of `s` where known.
+    pub fn toml(s: &str, cause: toml::de::Error) -> Self {
+        match cause.line_col() {
+            Some((line, _)) => AocError::parse(s.lines().nth(line).unwrap_or(""), cause).at_line(line + 1),
+            None => AocError::parse("", cause)
+        }
+    }
+
+    /// Attach the (1-based) line number to an error raised while parsing that line.
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            AocError::Io { path, cause, .. } => AocError::Io { path, line: Some(line), cause },
+            AocError::Parse { input, cause, .. } => AocError::Parse { line: Some(line), input, cause },
+            e => e
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io { path, line: Some(line), cause } => write!(f, "{}:{}: {}", path, line, cause),
+            AocError::Io { path, line: None, cause } => write!(f, "{}: {}", path, cause),
+            AocError::Parse { line: Some(line), input, cause } if input.is_empty() => write!(f, "line {}: {}", line, cause),
+            AocError::Parse { line: Some(line), input, cause } => write!(f, "line {}: {}: '{}'", line, cause, input),
+            AocError::Parse { line: None, input, cause } if input.is_empty() => write!(f, "{}", cause),
+            AocError::Parse { line: None, input, cause } => write!(f, "{}: '{}'", cause, input),
+            AocError::Overflow(what) => write!(f, "arithmetic overflow: {}", what),
+            AocError::NoSolution(why) => write!(f, "no solution: {}", why),
+            AocError::InvalidPart(part) => write!(f, "invalid part {}, expected 1 or 2", part),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { cause, .. } => Some(cause),
+            _ => None
+        }
+    }
+}
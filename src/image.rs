@@ -0,0 +1,152 @@
+use std::convert::TryFrom;
+use std::io::{self, Write};
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// Simple RGB raster, written out as binary PPM or PNG.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Image {
+        Image { width, height, pixels: vec![BLACK; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// Every pixel blown up to a `factor` by `factor` square.
+    pub fn scale(&self, factor: usize) -> Image {
+        let factor = factor.max(1);
+        let mut scaled = Image::new(self.width * factor, self.height * factor);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.set(x, y, self.get(x / factor, y / factor));
+            }
+        }
+        scaled
+    }
+
+    pub fn write_ppm(&self, w: &mut dyn Write) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.pixels.concat())
+    }
+
+    pub fn write_png(&self, w: &mut dyn Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels.concat()))
+            .map_err(io::Error::other)
+    }
+}
+
+/// Greyscale raster as binary PBM: black for 0, white for anything else.
+pub fn write_pbm(w: &mut dyn Write, width: usize, height: usize, grey: &[u8]) -> io::Result<()> {
+    write!(w, "P4\n{} {}\n", width, height)?;
+    let mut row = vec![0u8; width.div_ceil(8)];
+    for line in grey.chunks(width.max(1)).take(height) {
+        row.iter_mut().for_each(|b| *b = 0);
+        for (x, level) in line.iter().enumerate() {
+            // a set bit is black
+            if *level == 0 {
+                row[x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+        w.write_all(&row)?;
+    }
+    Ok(())
+}
+
+/// Greyscale raster as binary PGM.
+pub fn write_pgm(w: &mut dyn Write, width: usize, height: usize, grey: &[u8]) -> io::Result<()> {
+    write!(w, "P5\n{} {}\n255\n", width, height)?;
+    w.write_all(&grey[..width * height])
+}
+
+/// Animated greyscale GIF that loops forever.
+pub struct GreyGif<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: u16,
+    height: u16,
+    /// Time per frame, in hundredths of a second.
+    delay: u16,
+}
+
+impl<W: Write> GreyGif<W> {
+    pub fn new(w: W, width: usize, height: usize, delay: u16) -> io::Result<GreyGif<W>> {
+        let size = |n: usize| u16::try_from(n)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("{}x{} too large for a GIF", width, height)));
+        let (width, height) = (size(width)?, size(height)?);
+        let palette: Vec<u8> = (0..=255u8).flat_map(|g| [g, g, g]).collect();
+        let mut encoder = gif::Encoder::new(w, width, height, &palette).map_err(io::Error::other)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+        Ok(GreyGif { encoder, width, height, delay })
+    }
+
+    pub fn add_frame(&mut self, grey: &[u8]) -> io::Result<()> {
+        let mut frame = gif::Frame::from_indexed_pixels(self.width, self.height, grey, None);
+        frame.delay = self.delay;
+        self.encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ppm() {
+        let mut image = Image::new(2, 1);
+        image.set(1, 0, WHITE);
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\0\0\0\xff\xff\xff");
+    }
+
+    #[test]
+    fn test_png() {
+        let mut png = Vec::new();
+        Image::new(3, 2).scale(2).write_png(&mut png).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn test_greyscale() {
+        let grey = [0, 255, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut pbm = Vec::new();
+        write_pbm(&mut pbm, 9, 2, &grey).unwrap();
+        assert_eq!(pbm, b"P4\n9 2\n\xbf\x80\x7f\x80");
+        let mut pgm = Vec::new();
+        write_pgm(&mut pgm, 9, 2, &grey).unwrap();
+        assert_eq!(&pgm[..11], b"P5\n9 2\n255\n");
+        assert_eq!(&pgm[11..], &grey);
+        let mut gif = Vec::new();
+        let mut frames = GreyGif::new(&mut gif, 9, 2, 10).unwrap();
+        frames.add_frame(&grey).unwrap();
+        frames.add_frame(&[128; 18]).unwrap();
+        drop(frames);
+        assert_eq!(&gif[..6], b"GIF89a");
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::str;
+use crate::AocError;
+
+/// File name that reads from standard input instead.
+pub const STDIN: &str = "-";
+
+/// Open `filename` for buffered reading, `-` being standard input.
+pub fn reader(filename: &str) -> Result<Box<dyn BufRead>, AocError> {
+    if filename == STDIN {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        let file = File::open(filename).map_err(|e| AocError::io(filename, None, e))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Streaming iterator over the lines of a file, see `lines`.
+pub struct Lines {
+    filename: String,
+    lines: io::Lines<Box<dyn BufRead>>,
+    line: usize,
+}
+
+impl Iterator for Lines {
+    type Item = Result<String, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        self.line += 1;
+        Some(line.map_err(|e| AocError::io(&self.filename, Some(self.line), e)))
+    }
+}
+
+/// Iterate over the lines of `filename` without keeping the whole file in memory.
+pub fn lines(filename: &str) -> Result<Lines, AocError> {
+    Ok(Lines {
+        filename: filename.to_string(),
+        lines: reader(filename)?.lines(),
+        line: 0,
+    })
+}
+
+/// Whole contents of an input file, read in one go.
+pub struct Input {
+    filename: String,
+    bytes: Vec<u8>,
+}
+
+impl Input {
+    pub fn read(filename: &str) -> Result<Input, AocError> {
+        let mut bytes = Vec::new();
+        if filename != STDIN {
+            if let Ok(meta) = std::fs::metadata(filename) {
+                bytes.reserve(meta.len() as usize);
+            }
+        }
+        reader(filename)?
+            .read_to_end(&mut bytes)
+            .map_err(|e| AocError::io(filename, None, e))?;
+        Ok(Input { filename: filename.to_string(), bytes })
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The contents as text, failing with the line of the first invalid UTF-8 byte.
+    pub fn as_str(&self) -> Result<&str, AocError> {
+        str::from_utf8(&self.bytes).map_err(|e| {
+            let valid = &self.bytes[..e.valid_up_to()];
+            let line = valid.iter().filter(|b| **b == b'\n').count() + 1;
+            AocError::io(&self.filename, Some(line), io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+    }
+
+    pub fn into_string(self) -> Result<String, AocError> {
+        self.as_str()?;
+        Ok(String::from_utf8(self.bytes).expect("validated UTF-8"))
+    }
+
+    pub fn lines(&self) -> Result<str::Lines<'_>, AocError> {
+        Ok(self.as_str()?.lines())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(bytes: &[u8]) -> Input {
+        Input { filename: "test".to_string(), bytes: bytes.to_vec() }
+    }
+
+    #[test]
+    fn test_lines() {
+        let input = input(b"a\r\nb\nc");
+        let lines: Vec<&str> = input.lines().unwrap().collect();
+        assert_eq!(lines, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        match input(b"a\nb\n\xffc\n").as_str() {
+            Err(AocError::Io { line, .. }) => assert_eq!(line, Some(3)),
+            _ => panic!("expected invalid UTF-8")
+        }
+    }
+
+    #[test]
+    fn test_streaming_lines() {
+        let lines: Vec<String> = lines("data/day9.txt").unwrap()
+            .map(|l| l.unwrap())
+            .collect();
+        assert_eq!(lines.len(), 28);
+    }
+}
@@ -1,28 +1,125 @@
-use std::fs::File;
-use std::io::{BufReader, BufRead};
-use regex::Captures;
-
-pub fn load(filename: &str) -> Vec<String> {
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    reader.lines()
-        .map(|l| l.unwrap())
-        .collect()
-}
-
-pub fn str_from_capture(captures: &Captures, i: usize) -> String {
-    match captures.get(i) {
-        Some(m) => m.as_str().to_string(),
-        None => unreachable!("expected string for capture {}", i)
+use std::fmt::Display;
+use std::str::FromStr;
+use regex::{Captures, Match, Regex};
+
+mod error;
+pub use error::AocError;
+
+/// Implements `FromCaptures` for a struct by parsing each field from the
+/// capture group with the same name, e.g. `from_captures!(Route { from, to, dist })`.
+macro_rules! from_captures {
+    ($name:ident { $($field:ident),* $(,)? }) => {
+        impl $crate::FromCaptures for $name {
+            fn from_captures(caps: &regex::Captures) -> Result<Self, $crate::AocError> {
+                Ok($name {
+                    $($field: $crate::capture_named(caps, stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
+
+pub mod answers;
+pub mod bench;
+pub mod image;
+pub mod input;
+pub mod solver;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+
+pub fn load(filename: &str) -> Result<Vec<String>, AocError> {
+    input::lines(filename)?.collect()
+}
+
+pub fn load_bytes(filename: &str) -> Result<Vec<u8>, AocError> {
+    input::Input::read(filename).map(|i| i.into_bytes())
+}
+
+pub fn load_string(filename: &str) -> Result<String, AocError> {
+    input::Input::read(filename)?.into_string()
+}
+
+/// A type that can be built from the capture groups of a regex match.
+pub trait FromCaptures: Sized {
+    fn from_captures(caps: &Captures) -> Result<Self, AocError>;
+}
+
+fn parse_match<T>(caps: &Captures, m: Option<Match>, group: &dyn Display) -> Result<T, AocError>
+    where T: FromStr, T::Err: Display {
+    let whole = caps.get(0).map_or("", |m| m.as_str());
+    match m {
+        Some(m) => m.as_str().parse()
+            .map_err(|e| AocError::parse(whole, format!("capture {}: {}", group, e))),
+        None => Err(AocError::parse(whole, format!("missing capture {}", group)))
+    }
+}
+
+pub fn capture<T>(caps: &Captures, i: usize) -> Result<T, AocError>
+    where T: FromStr, T::Err: Display {
+    parse_match(caps, caps.get(i), &i)
+}
+
+pub fn capture_named<T>(caps: &Captures, name: &str) -> Result<T, AocError>
+    where T: FromStr, T::Err: Display {
+    parse_match(caps, caps.name(name), &name)
+}
+
+/// Match `line` against `re` and build a `T` from the capture groups.
+pub fn parse_captures<T: FromCaptures>(re: &Regex, line: &str) -> Result<T, AocError> {
+    match re.captures(line) {
+        Some(caps) => T::from_captures(&caps),
+        None => Err(AocError::parse(line, format!("no match for '{}'", re)))
     }
 }
 
-pub fn int_from_capture(captures: &Captures, i: usize) -> u32 {
-    match captures.get(i) {
-        Some(m) => match m.as_str().parse() {
-            Ok(ival) => ival,
-            Err(_) => unreachable!("expected int for capture {}: {}", i, m.as_str())
-        },
-        None => unreachable!("expected int for capture {}", i)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Move {
+        dir: char,
+        steps: u32,
+    }
+
+    from_captures!(Move { dir, steps });
+
+    fn move_re() -> Regex {
+        Regex::new(r"(?P<dir>[a-z])(?P<steps>-?\d+)(?P<sign>!)?").unwrap()
+    }
+
+    #[test]
+    fn test_capture() {
+        let re = move_re();
+        let caps = re.captures("u12!").unwrap();
+        assert_eq!(capture::<char>(&caps, 1).unwrap(), 'u');
+        assert_eq!(capture::<u32>(&caps, 2).unwrap(), 12);
+        assert_eq!(capture_named::<u32>(&caps, "steps").unwrap(), 12);
+        let caps = re.captures("u-3").unwrap();
+        let e = capture::<String>(&caps, 3).unwrap_err();
+        assert_eq!(e.to_string(), "missing capture 3: 'u-3'");
+        let e = capture_named::<u32>(&caps, "steps").unwrap_err();
+        assert!(e.to_string().starts_with("capture steps: "), "{}", e);
+        assert!(capture::<u32>(&caps, 9).is_err());
+    }
+
+    #[test]
+    fn test_parse_captures() {
+        let re = move_re();
+        assert_eq!(parse_captures::<Move>(&re, "d7").unwrap(), Move { dir: 'd', steps: 7 });
+        let e = parse_captures::<Move>(&re, "7").unwrap_err();
+        assert!(e.to_string().starts_with("no match for "), "{}", e);
+        let e = parse_captures::<Move>(&re, "d99999999999").unwrap_err();
+        assert!(e.to_string().starts_with("capture steps: "), "{}", e);
     }
 }
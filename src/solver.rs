@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+use crate::AocError;
+use crate::{day1, day10, day11, day12, day2, day3, day4, day5, day6, day7, day8, day9};
+
+/// Solution for the two parts of a single day.
+pub trait Solver {
+    /// Puzzle input after parsing, shared by both parts.
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError>;
+    fn part1(&self, input: &Self::Input) -> Result<String, AocError>;
+    fn part2(&self, input: &Self::Input) -> Result<String, AocError>;
+}
+
+/// Answer of a single run, with the time spent parsing and solving.
+pub struct Timed {
+    pub answer: String,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Object safe view on a `Solver`, so all days can be kept in one registry.
+pub trait Runner {
+    fn run_timed(&self, part: u32, input: &str) -> Result<Timed, AocError>;
+
+    fn run(&self, part: u32, input: &str) -> Result<String, AocError> {
+        self.run_timed(part, input).map(|t| t.answer)
+    }
+}
+
+impl<S: Solver> Runner for S {
+    fn run_timed(&self, part: u32, input: &str) -> Result<Timed, AocError> {
+        if part != 1 && part != 2 {
+            return Err(AocError::InvalidPart(part));
+        }
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse = start.elapsed();
+        let start = Instant::now();
+        let answer = if part == 1 { self.part1(&parsed)? } else { self.part2(&parsed)? };
+        let solve = start.elapsed();
+        Ok(Timed { answer, parse, solve })
+    }
+}
+
+pub const DAYS: u32 = 12;
+
+pub fn solver(day: u32) -> Option<Box<dyn Runner>> {
+    match day {
+        1 => Some(Box::new(day1::Day1)),
+        2 => Some(Box::new(day2::Day2)),
+        3 => Some(Box::new(day3::Day3)),
+        4 => Some(Box::new(day4::Day4)),
+        5 => Some(Box::new(day5::Day5)),
+        6 => Some(Box::new(day6::Day6)),
+        7 => Some(Box::new(day7::Day7)),
+        8 => Some(Box::new(day8::Day8)),
+        9 => Some(Box::new(day9::Day9)),
+        10 => Some(Box::new(day10::Day10)),
+        11 => Some(Box::new(day11::Day11)),
+        12 => Some(Box::new(day12::Day12)),
+        _ => None
+    }
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+use aoc_2015::load_string;
+use aoc_2015::answers::{Answers, ANSWERS};
+use aoc_2015::solver::{solver, DAYS};
+
+#[test]
+fn test_known_answers() {
+    let answers = Answers::load(ANSWERS).expect("answers file");
+    for day in 1..=DAYS {
+        let input = format!("data/day{}.txt", day);
+        if !Path::new(&input).exists() {
+            eprintln!("skipping day {}: no {}", day, input);
+            continue;
+        }
+        let data = load_string(&input).expect("readable input");
+        let runner = solver(day).expect("registered solver");
+        for part in 1..=2 {
+            match answers.get(day, part) {
+                Some(expected) => assert_eq!(runner.run(part, &data).unwrap(), expected,
+                                             "day {} part {}", day, part),
+                None => eprintln!("skipping day {} part {}: no known answer", day, part)
+            }
+        }
+    }
+}